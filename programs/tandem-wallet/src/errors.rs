@@ -38,4 +38,122 @@ pub enum VaultError {
     OnlyAuthority,
     #[msg("Fee basis points must be <= 10000")]
     InvalidFeeBps,
+    #[msg("Daily budget must be less than or equal to weekly budget")]
+    InvalidBudgets,
+    #[msg("Amount exceeds the remaining daily budget")]
+    DailyBudgetExceeded,
+    #[msg("Amount exceeds the remaining weekly budget")]
+    WeeklyBudgetExceeded,
+    #[msg("Whitelist limits are inconsistent")]
+    InvalidWhitelistLimits,
+    #[msg("Amount exceeds the whitelist entry's per-transaction limit")]
+    WhitelistTxLimitExceeded,
+    #[msg("Amount exceeds the whitelist entry's remaining period limit")]
+    WhitelistPeriodLimitExceeded,
+    #[msg("Delay must not be negative")]
+    InvalidDelay,
+    #[msg("Whitelist expiry must be after the entry becomes active")]
+    InvalidWhitelistExpiry,
+    #[msg("Whitelist entry is already active")]
+    WhitelistEntryActive,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Proposal has not expired")]
+    ProposalNotExpired,
+    #[msg("Only a vault approver can perform this action")]
+    OnlyApprover,
+    #[msg("Approver has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Address is already an approver")]
+    AlreadyApprover,
+    #[msg("Address is not an approver")]
+    NotApprover,
+    #[msg("Approver set is full")]
+    TooManyApprovers,
+    #[msg("Approval threshold must be between 1 and the number of approvers")]
+    InvalidApprovalThreshold,
+    #[msg("Agent is paused")]
+    AgentPaused,
+    #[msg("Agent is not allowed to create proposals")]
+    AgentCannotPropose,
+    #[msg("Address is already the vault's primary agent")]
+    AlreadyPrimaryAgent,
+    #[msg("Account is not in a legacy layout that can be migrated")]
+    NotLegacyAccount,
+    #[msg("No human ownership transfer is pending")]
+    NoPendingHumanTransfer,
+    #[msg("Only the pending human can accept ownership")]
+    OnlyPendingHuman,
+    #[msg("Guardian set or threshold is invalid")]
+    InvalidGuardians,
+    #[msg("Only a vault guardian can perform this action")]
+    OnlyGuardian,
+    #[msg("Recovery has not reached its threshold or its timelock has not elapsed")]
+    RecoveryNotReady,
+    #[msg("The vault's USDC mint is configured on the vault itself")]
+    CannotConfigureUsdcMint,
+    #[msg("Mint is not enabled on this vault")]
+    MintNotConfigured,
+    #[msg("Token account does not match the expected mint or owner")]
+    InvalidTokenAccount,
+    #[msg("Vault token account still holds a balance")]
+    MintBalanceNotEmpty,
+    #[msg("Deposit reference exceeds 64 characters")]
+    ReferenceTooLong,
+    #[msg("Account is not a whitelist entry, proposal, agent role or agent ledger of this vault")]
+    NotVaultAccount,
+    #[msg("Batch must have 1 to 16 lines, each with a recipient and whitelist account")]
+    InvalidBatch,
+    #[msg("Invalid payment schedule")]
+    InvalidSchedule,
+    #[msg("Subscription is paused")]
+    SubscriptionPaused,
+    #[msg("Subscription payment is not due yet")]
+    SubscriptionNotDue,
+    #[msg("Subscription has ended")]
+    SubscriptionEnded,
+    #[msg("Nothing has vested since the last withdrawal")]
+    NothingVested,
+    #[msg("Only the stream creator or the human can cancel")]
+    OnlyStreamCreator,
+    #[msg("Only the stream recipient can withdraw")]
+    OnlyStreamRecipient,
+    #[msg("Signer is not allowed to settle this escrow")]
+    NotEscrowParty,
+    #[msg("Escrow deadline has not passed")]
+    EscrowNotExpired,
+    #[msg("Memo or note is too long")]
+    MemoTooLong,
+    #[msg("Only the agent that created the proposal can change it")]
+    OnlyProposer,
+    #[msg("Proposal was amended after the approved revision")]
+    ProposalRevisionMismatch,
+    #[msg("Approved amount must be between 1 and the requested amount")]
+    InvalidApprovedAmount,
+    #[msg("Proposal is already approved and scheduled for execution")]
+    ProposalScheduled,
+    #[msg("Proposal is not scheduled for execution")]
+    ProposalNotScheduled,
+    #[msg("Execution delay has not passed")]
+    ExecutionDelayNotElapsed,
+    #[msg("Optimistic cap must be above tier2_max with a non-zero veto period shorter than the proposal TTL")]
+    InvalidOptimisticBand,
+    #[msg("Batch must have 1 to 10 writable proposals, each with its accounts")]
+    InvalidProposalBatch,
+    #[msg("Proposal nonce must have its top bit set")]
+    InvalidProposalNonce,
+    #[msg("Cancel the vault's subscriptions, streams and escrows, remove its mints and pass all its whitelist entries, proposals, agent roles and ledgers first")]
+    VaultHasOpenAccounts,
+    #[msg("Sends must say what the payment is for")]
+    MemoRequired,
+    #[msg("Proposal is no longer within the vault's optimistic band")]
+    OutsideOptimisticBand,
+    #[msg("Proposal asks for a different amount than the approver saw")]
+    ProposalAmountMismatch,
+    #[msg("Nonce-keyed proposals need their proposer's agent ledger")]
+    MissingAgentLedger,
+    #[msg("Invoice ID is too long")]
+    InvoiceIdTooLong,
+    #[msg("Batch approval only covers USDC proposals; approve others one at a time")]
+    BatchUsdcOnly,
 }
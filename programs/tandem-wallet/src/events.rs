@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::CancelReason;
 
 #[event]
 pub struct VaultInitialized {
@@ -8,6 +9,49 @@ pub struct VaultInitialized {
     pub usdc_mint: Pubkey,
 }
 
+#[event]
+pub struct Deposited {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    /// Amount credited to the vault, net of any token transfer fee.
+    pub amount: u64,
+    /// Free-form reference supplied by the depositor (e.g. an invoice number).
+    pub reference: Option<String>,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub vault: Pubkey,
+    pub human: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub vault: Pubkey,
+    pub human: Pubkey,
+    /// USDC swept to the human before closing.
+    pub swept: u64,
+    pub whitelist_entries: u32,
+    pub proposals: u32,
+    pub agent_roles: u32,
+}
+
+#[event]
+pub struct VaultReconciled {
+    pub vault: Pubkey,
+    pub total_deposited: u64,
+    pub total_sent: u64,
+    pub total_fees_paid: u64,
+    /// Balance implied by the counters: deposited - sent - fees.
+    pub expected_balance: i128,
+    /// Actual balance of the vault's USDC account.
+    pub actual_balance: u64,
+    /// actual - expected. Positive means untracked inflows (e.g. raw transfers).
+    pub difference: i128,
+}
+
 #[event]
 pub struct UsdcSent {
     pub vault: Pubkey,
@@ -17,30 +61,197 @@ pub struct UsdcSent {
     pub fee: u64,
     pub tier: u8,
     pub whitelisted: bool,
+    /// Remaining daily budget after this send (u64::MAX when unlimited).
+    pub daily_remaining: u64,
+    /// Remaining weekly budget after this send (u64::MAX when unlimited).
+    pub weekly_remaining: u64,
+    /// Purpose of the payment, also written through SPL Memo.
+    pub memo: String,
+    /// Invoice the payment settles, appended to the SPL Memo as `| invoice:<id>`.
+    pub invoice_id: Option<String>,
+}
+
+#[event]
+pub struct UsdcBatchSent {
+    pub vault: Pubkey,
+    pub signer: Pubkey,
+    pub lines: u8,
+    /// Sum of all lines as received by the recipients.
+    pub total: u64,
+    /// Fee charged once on the total.
+    pub fee: u64,
+    /// Tier of the batch's non-whitelisted total.
+    pub tier: u8,
+    pub daily_remaining: u64,
+    pub weekly_remaining: u64,
+    /// Purpose of the batch, also written through SPL Memo.
+    pub memo: String,
+    /// Invoice the batch settles, appended to the SPL Memo as `| invoice:<id>`.
+    pub invoice_id: Option<String>,
+}
+
+#[event]
+pub struct BatchLineSent {
+    pub vault: Pubkey,
+    pub signer: Pubkey,
+    pub index: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub tier: u8,
+    pub whitelisted: bool,
+}
+
+#[event]
+pub struct TokenSent {
+    pub vault: Pubkey,
+    pub signer: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub tier: u8,
+    /// Remaining daily budget for this mint after the send (u64::MAX when unlimited).
+    pub daily_remaining: u64,
+    /// Remaining weekly budget for this mint after the send (u64::MAX when unlimited).
+    pub weekly_remaining: u64,
+    /// Purpose of the payment, also written through SPL Memo.
+    pub memo: String,
+    /// Invoice the payment settles, appended to the SPL Memo as `| invoice:<id>`.
+    pub invoice_id: Option<String>,
 }
 
 #[event]
 pub struct ProposalCreated {
     pub vault: Pubkey,
     pub proposal_id: u64,
+    /// Display number: the ID for sequential proposals, the proposer's count for nonce-keyed ones.
+    pub sequence: u64,
+    pub agent: Pubkey,
+    pub mint: Pubkey,
     pub recipient: Pubkey,
     pub amount: u64,
     pub memo: String,
+    pub expires_at: Option<i64>,
+    /// The vault's optimistic band at creation: (tier2_max, optimistic_max], zero cap when disabled.
+    pub optimistic_max: u64,
+    pub veto_period: i64,
+    /// Whether this proposal is in the band and executable without approval from `executable_at`.
+    pub optimistic: bool,
+    pub executable_at: Option<i64>,
+}
+
+#[event]
+pub struct ProposalApprovalRecorded {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
 }
 
 #[event]
 pub struct ProposalApproved {
     pub vault: Pubkey,
+    /// A withdrawn nonce can be proposed again; `sequence` tells the two apart.
     pub proposal_id: u64,
+    pub sequence: u64,
+    pub mint: Pubkey,
     pub recipient: Pubkey,
+    /// Amount the agent asked for.
+    pub requested_amount: u64,
+    /// Amount the approvers granted, at most `requested_amount`.
+    pub approved_amount: u64,
+    /// Amount the recipient received (`approved_amount` net of any transfer fee).
     pub amount: u64,
     pub fee: u64,
 }
 
+#[event]
+pub struct ProposalScheduled {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub approved_amount: u64,
+    pub executable_at: i64,
+}
+
 #[event]
 pub struct ProposalCancelled {
+    pub vault: Pubkey,
+    /// A withdrawn nonce can be proposed again; `sequence` tells the two apart.
+    pub proposal_id: u64,
+    pub sequence: u64,
+    pub cancelled_by: Pubkey,
+    pub reason: CancelReason,
+    pub note: String,
+}
+
+#[event]
+pub struct ProposalAmended {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub revision: u32,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub memo: String,
+    /// Moved forward when a fresh veto period would otherwise outlast the proposal.
+    pub expires_at: Option<i64>,
+    pub executable_at: Option<i64>,
+}
+
+#[event]
+pub struct ProposalWithdrawn {
     pub vault: Pubkey,
     pub proposal_id: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct ExpiredProposalClosed {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub closer: Pubkey,
+}
+
+#[event]
+pub struct ProposalTtlUpdated {
+    pub vault: Pubkey,
+    pub proposal_ttl: i64,
+}
+
+#[event]
+pub struct ExecutionDelayUpdated {
+    pub vault: Pubkey,
+    pub execution_delay: i64,
+    /// When `execution_delay` applies; later than the update when the delay was lowered.
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct OptimisticBandUpdated {
+    pub vault: Pubkey,
+    pub optimistic_max: u64,
+    pub veto_period: i64,
+}
+
+#[event]
+pub struct ApproverAdded {
+    pub vault: Pubkey,
+    pub approver: Pubkey,
+    pub approval_threshold: u8,
+}
+
+#[event]
+pub struct ApproverRemoved {
+    pub vault: Pubkey,
+    pub approver: Pubkey,
+    pub approval_threshold: u8,
+}
+
+#[event]
+pub struct ApprovalThresholdUpdated {
+    pub vault: Pubkey,
+    pub approval_threshold: u8,
 }
 
 #[event]
@@ -50,10 +261,57 @@ pub struct TiersUpdated {
     pub tier2_max: u64,
 }
 
+#[event]
+pub struct BudgetsUpdated {
+    pub vault: Pubkey,
+    pub daily_limit: u64,
+    pub weekly_limit: u64,
+}
+
+#[event]
+pub struct MintAdded {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub vault_ata: Pubkey,
+    pub tier1_max: u64,
+    pub tier2_max: u64,
+}
+
+#[event]
+pub struct MintLimitsUpdated {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub tier1_max: u64,
+    pub tier2_max: u64,
+    pub daily_limit: u64,
+    pub weekly_limit: u64,
+}
+
+#[event]
+pub struct MintRemoved {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+}
+
 #[event]
 pub struct WhitelistAdded {
     pub vault: Pubkey,
     pub address: Pubkey,
+    pub max_per_tx: u64,
+    pub max_per_period: u64,
+    pub period_seconds: i64,
+    pub active_from: i64,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct WhitelistUpdated {
+    pub vault: Pubkey,
+    pub address: Pubkey,
+    pub max_per_tx: u64,
+    pub max_per_period: u64,
+    pub period_seconds: i64,
+    pub active_from: i64,
 }
 
 #[event]
@@ -62,6 +320,238 @@ pub struct WhitelistRemoved {
     pub address: Pubkey,
 }
 
+#[event]
+pub struct WhitelistCancelled {
+    pub vault: Pubkey,
+    pub address: Pubkey,
+}
+
+#[event]
+pub struct WhitelistDelayUpdated {
+    pub vault: Pubkey,
+    pub delay: i64,
+    /// When `delay` applies; later than the update when the delay was lowered.
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct AgentAdded {
+    pub vault: Pubkey,
+    pub agent: Pubkey,
+    pub tier1_max: u64,
+    pub tier2_max: u64,
+    pub can_propose: bool,
+}
+
+#[event]
+pub struct AgentUpdated {
+    pub vault: Pubkey,
+    pub agent: Pubkey,
+    pub tier1_max: u64,
+    pub tier2_max: u64,
+    pub paused: bool,
+    pub can_propose: bool,
+}
+
+#[event]
+pub struct AgentRotated {
+    pub vault: Pubkey,
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+}
+
+#[event]
+pub struct VaultMigrated {
+    pub vault: Pubkey,
+    pub whitelist_entries: u32,
+    pub proposals: u32,
+}
+
+#[event]
+pub struct VaultAccountsMigrated {
+    pub vault: Pubkey,
+    pub whitelist_entries: u32,
+    pub proposals: u32,
+}
+
+#[event]
+pub struct AgentRemoved {
+    pub vault: Pubkey,
+    pub agent: Pubkey,
+}
+
+#[event]
+pub struct AgentLedgerOpened {
+    pub vault: Pubkey,
+    pub agent: Pubkey,
+}
+
+#[event]
+pub struct HumanTransferProposed {
+    pub vault: Pubkey,
+    pub human: Pubkey,
+    pub pending_human: Pubkey,
+}
+
+#[event]
+pub struct HumanTransferCancelled {
+    pub vault: Pubkey,
+    pub pending_human: Pubkey,
+}
+
+#[event]
+pub struct HumanTransferred {
+    pub vault: Pubkey,
+    pub old_human: Pubkey,
+    pub new_human: Pubkey,
+}
+
+#[event]
+pub struct GuardiansUpdated {
+    pub vault: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub guardian_threshold: u8,
+    pub recovery_delay: i64,
+}
+
+#[event]
+pub struct RecoveryInitiated {
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+    pub new_human: Pubkey,
+}
+
+#[event]
+pub struct RecoveryApproved {
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub executable_at: Option<i64>,
+}
+
+#[event]
+pub struct RecoveryVetoed {
+    pub vault: Pubkey,
+    pub new_human: Pubkey,
+}
+
+#[event]
+pub struct RecoveryExecuted {
+    pub vault: Pubkey,
+    pub old_human: Pubkey,
+    pub new_human: Pubkey,
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub vault: Pubkey,
+    pub subscription_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub start_at: i64,
+    pub end_at: Option<i64>,
+    pub max_payments: Option<u32>,
+}
+
+#[event]
+pub struct SubscriptionPaid {
+    pub vault: Pubkey,
+    pub subscription_id: u64,
+    pub cranker: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub payments_made: u32,
+    pub next_payment_at: i64,
+}
+
+#[event]
+pub struct SubscriptionPauseUpdated {
+    pub vault: Pubkey,
+    pub subscription_id: u64,
+    pub paused: bool,
+    pub next_payment_at: i64,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub vault: Pubkey,
+    pub subscription_id: u64,
+    pub payments_made: u32,
+}
+
+#[event]
+pub struct StreamCreated {
+    pub vault: Pubkey,
+    pub stream_id: u64,
+    pub creator: Pubkey,
+    pub recipient: Pubkey,
+    pub rate_per_second: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub deposited: u64,
+    /// Protocol fee moved into the stream on top of `deposited`.
+    pub fee_reserve: u64,
+    pub tier: u8,
+}
+
+#[event]
+pub struct StreamWithdrawn {
+    pub vault: Pubkey,
+    pub stream_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    /// Total withdrawn from the stream so far.
+    pub withdrawn: u64,
+}
+
+#[event]
+pub struct StreamCancelled {
+    pub vault: Pubkey,
+    pub stream_id: u64,
+    pub cancelled_by: Pubkey,
+    /// Vested amount paid out to the recipient on cancel.
+    pub paid_out: u64,
+    /// Unvested amount returned to the vault.
+    pub returned: u64,
+}
+
+#[event]
+pub struct EscrowCreated {
+    pub vault: Pubkey,
+    pub escrow_id: u64,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub arbiter: Option<Pubkey>,
+    pub amount: u64,
+    /// Protocol fee moved into the escrow on top of `amount`.
+    pub fee_reserve: u64,
+    pub deadline: i64,
+    pub tier: u8,
+}
+
+#[event]
+pub struct EscrowReleased {
+    pub vault: Pubkey,
+    pub escrow_id: u64,
+    pub released_by: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct EscrowRefunded {
+    pub vault: Pubkey,
+    pub escrow_id: u64,
+    pub refunded_by: Pubkey,
+    /// Amount returned to the vault.
+    pub amount: u64,
+}
+
 #[event]
 pub struct VaultPausedEvent {
     pub vault: Pubkey,
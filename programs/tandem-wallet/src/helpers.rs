@@ -1,72 +1,559 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, Create};
+use anchor_spl::memo::{self, BuildMemo, Memo};
+use anchor_spl::token_interface::{
+    self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use anchor_spl::token_interface::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+};
 use crate::state::*;
 use crate::errors::*;
 
 const REWARD_PRECISION: u128 = 1_000_000_000_000; // 1e12
 
+/// Check a send's memo and optional invoice ID before any funds move.
+pub fn check_payment_memo(memo: &str, invoice_id: Option<&str>) -> Result<()> {
+    require!(!memo.is_empty(), VaultError::MemoRequired);
+    require!(memo.len() <= MAX_MEMO_LEN, VaultError::MemoTooLong);
+    if let Some(invoice_id) = invoice_id {
+        require!(invoice_id.len() <= MAX_INVOICE_ID_LEN, VaultError::InvoiceIdTooLong);
+    }
+    Ok(())
+}
+
+/// Write a send's purpose to the transaction log through SPL Memo, so indexers
+/// see it next to the transfer.
+pub fn write_payment_memo<'info>(
+    memo_program: &Program<'info, Memo>,
+    memo: &str,
+    invoice_id: Option<&str>,
+) -> Result<()> {
+    let memo_text = match invoice_id {
+        Some(invoice_id) => format!("{} | invoice:{}", memo, invoice_id),
+        None => memo.to_string(),
+    };
+    memo::build_memo(
+        CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+        memo_text.as_bytes(),
+    )
+}
+
+/// Protocol fee on `amount` at `fee_bps`, rounded down.
+pub fn protocol_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(VaultError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(VaultError::Overflow)? as u64;
+    Ok(fee)
+}
+
 /// Calculate fee and transfer to staker reward + buyback ATAs.
+/// Staker rewards are paid in USDC only, so fees in other mints pass `None`
+/// for `staker_reward_ata` and go entirely to buyback.
 /// Returns the total fee amount deducted.
 pub fn calculate_and_transfer_fee<'info>(
     amount: u64,
     fee_bps: u16,
     total_staked: u64,
-    vault_usdc_ata: &Account<'info, TokenAccount>,
-    staker_reward_ata: &Account<'info, TokenAccount>,
-    buyback_ata: &Account<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault_ata: &InterfaceAccount<'info, TokenAccount>,
+    staker_reward_ata: Option<&InterfaceAccount<'info, TokenAccount>>,
+    buyback_ata: &InterfaceAccount<'info, TokenAccount>,
     vault_authority: &AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
     signer_seeds: &[&[&[u8]]],
 ) -> Result<u64> {
-    let fee = (amount as u128)
-        .checked_mul(fee_bps as u128)
-        .ok_or(VaultError::Overflow)?
-        .checked_div(10_000)
-        .ok_or(VaultError::Overflow)? as u64;
+    let fee = protocol_fee(amount, fee_bps)?;
 
     if fee == 0 {
         return Ok(0);
     }
 
     // If nobody is staked, send 100% to buyback to prevent orphaned rewards
-    let (staker_fee, buyback_fee) = if total_staked > 0 {
-        let sf = fee / 2;
-        (sf, fee - sf)
-    } else {
-        (0, fee)
+    let (staker_fee, buyback_fee) = match staker_reward_ata {
+        Some(_) if total_staked > 0 => {
+            let sf = fee / 2;
+            (sf, fee - sf)
+        }
+        _ => (0, fee),
     };
 
     // Transfer staker portion
-    if staker_fee > 0 {
+    if let Some(staker_reward_ata) = staker_reward_ata.filter(|_| staker_fee > 0) {
         let cpi_ctx = CpiContext::new_with_signer(
             token_program.to_account_info(),
-            Transfer {
-                from: vault_usdc_ata.to_account_info(),
+            TransferChecked {
+                from: vault_ata.to_account_info(),
+                mint: mint.to_account_info(),
                 to: staker_reward_ata.to_account_info(),
                 authority: vault_authority.clone(),
             },
             signer_seeds,
         );
-        token::transfer(cpi_ctx, staker_fee)?;
+        token_interface::transfer_checked(cpi_ctx, staker_fee, mint.decimals)?;
     }
 
     // Transfer buyback portion
     if buyback_fee > 0 {
         let cpi_ctx = CpiContext::new_with_signer(
             token_program.to_account_info(),
-            Transfer {
-                from: vault_usdc_ata.to_account_info(),
+            TransferChecked {
+                from: vault_ata.to_account_info(),
+                mint: mint.to_account_info(),
                 to: buyback_ata.to_account_info(),
                 authority: vault_authority.clone(),
             },
             signer_seeds,
         );
-        token::transfer(cpi_ctx, buyback_fee)?;
+        token_interface::transfer_checked(cpi_ctx, buyback_fee, mint.decimals)?;
     }
 
     Ok(fee)
 }
 
+/// Add a USDC payment and its fee to the vault's outflow counters.
+pub fn record_usdc_outflow(vault: &mut Vault, amount: u64, fee: u64) -> Result<()> {
+    vault.total_sent = vault.total_sent.checked_add(amount).ok_or(VaultError::Overflow)?;
+    vault.total_fees_paid = vault
+        .total_fees_paid
+        .checked_add(fee)
+        .ok_or(VaultError::Overflow)?;
+    Ok(())
+}
+
+/// Amount that lands in the destination when `amount` of `mint` is transferred,
+/// net of any fee withheld by a Token-2022 transfer-fee extension.
+pub fn amount_received(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(amount);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let withheld = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(VaultError::Overflow)?,
+        Err(_) => 0,
+    };
+
+    let received = amount.checked_sub(withheld).ok_or(VaultError::Overflow)?;
+    Ok(received)
+}
+
+/// Create `owner`'s associated token account for `mint` unless it already exists.
+/// `payer` funds the rent.
+pub fn create_ata_idempotent<'info>(
+    payer: AccountInfo<'info>,
+    ata: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    associated_token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        associated_token_program,
+        Create {
+            payer,
+            associated_token: ata,
+            authority: owner,
+            mint,
+            system_program,
+            token_program,
+        },
+    );
+    associated_token::create_idempotent(cpi_ctx)
+}
+
+/// Deserialize a token account passed unchecked, e.g. because it may only be
+/// created during the instruction.
+pub fn read_token_account(info: &AccountInfo, token_program: &Pubkey) -> Result<TokenAccount> {
+    require_keys_eq!(*info.owner, *token_program, VaultError::InvalidTokenAccount);
+    let data = info.try_borrow_data()?;
+    TokenAccount::try_deserialize(&mut &data[..])
+}
+
+/// Check that `info` is `owner`'s token account for `mint`, or, if it doesn't
+/// exist yet, the address `owner`'s associated token account will be created at.
+pub fn check_recipient_token_account(info: &AccountInfo, owner: &Pubkey, mint: &Pubkey) -> Result<()> {
+    let token_programs = [anchor_spl::token::ID, spl_token_2022::ID];
+    if info.data_is_empty() {
+        let is_ata = token_programs.iter().any(|program| {
+            get_associated_token_address_with_program_id(owner, mint, program) == info.key()
+        });
+        require!(is_ata, VaultError::InvalidTokenAccount);
+        return Ok(());
+    }
+
+    require!(token_programs.contains(info.owner), VaultError::InvalidTokenAccount);
+    let data = info.try_borrow_data()?;
+    let account = TokenAccount::try_deserialize(&mut &data[..])?;
+    require!(
+        account.mint == *mint && account.owner == *owner,
+        VaultError::InvalidTokenAccount
+    );
+    Ok(())
+}
+
+/// Tier limits and rights that apply to an agent signer.
+pub struct AgentLimits {
+    pub tier1_max: u64,
+    pub tier2_max: u64,
+    pub can_propose: bool,
+}
+
+/// Resolve an agent signer to its limits: the vault's own for the primary agent,
+/// or those on its `AgentRole` for additional agents. The role's PDA seeds must
+/// already be checked against the vault and signer by the accounts struct.
+pub fn agent_limits(vault: &Vault, agent: &Pubkey, role: Option<&AgentRole>) -> Result<AgentLimits> {
+    if *agent == vault.agent {
+        return Ok(AgentLimits {
+            tier1_max: vault.tier1_max,
+            tier2_max: vault.tier2_max,
+            can_propose: true,
+        });
+    }
+
+    match role {
+        Some(role) if role.agent == *agent => {
+            require!(!role.paused, VaultError::AgentPaused);
+            Ok(AgentLimits {
+                tier1_max: role.tier1_max,
+                tier2_max: role.tier2_max,
+                can_propose: role.can_propose,
+            })
+        }
+        _ => err!(VaultError::OnlyAgent),
+    }
+}
+
+/// Tier an agent payment of `amount` falls into under the given limits.
+/// Tier 2 needs the emergency flag; anything above it is rejected.
+pub fn agent_tier(amount: u64, limits: &AgentLimits, is_emergency: bool) -> Result<u8> {
+    if amount <= limits.tier1_max {
+        Ok(1)
+    } else if amount <= limits.tier2_max {
+        require!(is_emergency, VaultError::NotEmergency);
+        Ok(2)
+    } else {
+        err!(VaultError::TierTooHigh)
+    }
+}
+
+/// Count an agent send or approved proposal against a pair of rolling budgets,
+/// either the vault's own (USDC) or those on a `MintConfig`.
+pub fn record_budget_spend(
+    daily_budget: &mut SpendWindow,
+    weekly_budget: &mut SpendWindow,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(
+        daily_budget.record(amount, now),
+        VaultError::DailyBudgetExceeded
+    );
+    require!(
+        weekly_budget.record(amount, now),
+        VaultError::WeeklyBudgetExceeded
+    );
+    Ok(())
+}
+
+/// Load a proposal passed through remaining accounts and check it the way the
+/// single-proposal constraints do: a writable, pending proposal PDA of `vault`.
+pub fn load_pending_proposal<'info>(
+    info: &'info AccountInfo<'info>,
+    vault: &Pubkey,
+) -> Result<Account<'info, Proposal>> {
+    let proposal = Account::<Proposal>::try_from(info)?;
+    require_keys_eq!(proposal.vault, *vault, VaultError::NotVaultAccount);
+    let expected = Pubkey::create_program_address(
+        &[
+            Proposal::SEED_PREFIX,
+            vault.as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref(),
+            &[proposal.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| VaultError::NotVaultAccount)?;
+    require_keys_eq!(expected, info.key(), VaultError::NotVaultAccount);
+    require!(info.is_writable, VaultError::InvalidProposalBatch);
+    require!(!proposal.executed, VaultError::ProposalAlreadyExecuted);
+    require!(!proposal.cancelled, VaultError::ProposalAlreadyCancelled);
+    Ok(proposal)
+}
+
+/// Add an approver's vote to a proposal, dropping votes from keys no longer in
+/// the approver set. Returns the vote count and the threshold.
+pub fn record_proposal_vote(
+    vault: &Vault,
+    proposal: &mut Proposal,
+    approver: Pubkey,
+    amount: u64,
+) -> Result<(usize, usize)> {
+    let (approvals, amounts) = proposal
+        .approvals
+        .iter()
+        .copied()
+        .zip(proposal.approval_amounts.iter().copied())
+        .filter(|(a, _)| vault.is_approver(a))
+        .unzip();
+    proposal.approvals = approvals;
+    proposal.approval_amounts = amounts;
+    require!(
+        !proposal.approvals.contains(&approver),
+        VaultError::AlreadyApproved
+    );
+    proposal.approvals.push(approver);
+    proposal.approval_amounts.push(amount);
+    Ok((proposal.approvals.len(), vault.required_approvals()))
+}
+
+/// Take a closed proposal off the count it was added to: its proposer's ledger
+/// for nonce-keyed proposals, the vault's otherwise.
+pub fn record_proposal_closed(
+    vault: &mut Vault,
+    ledger: Option<&mut AgentLedger>,
+    proposal: &Proposal,
+) -> Result<()> {
+    if proposal.is_nonce_keyed() {
+        let ledger = ledger.ok_or(VaultError::MissingAgentLedger)?;
+        ledger.open_proposals = ledger.open_proposals.checked_sub(1).ok_or(VaultError::Overflow)?;
+    } else {
+        vault.open_proposals = vault.open_proposals.checked_sub(1).ok_or(VaultError::Overflow)?;
+    }
+    Ok(())
+}
+
+/// The human can cancel any pending proposal; guardians only one that is
+/// scheduled and not yet executable.
+pub fn check_proposal_canceller(vault: &Vault, proposal: &Proposal, signer: &Pubkey, now: i64) -> Result<()> {
+    if *signer != vault.human {
+        require!(
+            vault.guardians.contains(signer) && proposal.is_awaiting_execution(now),
+            VaultError::OnlyHuman
+        );
+    }
+    Ok(())
+}
+
+/// When a proposal of `amount` in `mint` becomes executable without approval:
+/// `Some` if it lies in the vault's optimistic band above the agent's tier 2.
+pub fn optimistic_executable_at(
+    vault: &Vault,
+    limits: &AgentLimits,
+    mint: &Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<Option<i64>> {
+    let in_band = vault.optimistic_max > 0
+        && *mint == vault.usdc_mint
+        && amount > limits.tier2_max
+        && amount <= vault.optimistic_max;
+    if !in_band {
+        return Ok(None);
+    }
+    let executable_at = now.checked_add(vault.veto_period).ok_or(VaultError::Overflow)?;
+    Ok(Some(executable_at))
+}
+
+/// Accounts an approved proposal pays out through, shared by `approve_proposal`
+/// and `execute_proposal`.
+pub struct ProposalPayout<'a, 'info> {
+    pub vault: &'a mut Account<'info, Vault>,
+    pub mint_config: Option<&'a mut MintConfig>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault_token_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub recipient_ata: AccountInfo<'info>,
+    pub protocol_config: &'a ProtocolConfig,
+    pub staker_reward_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub buyback_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_buyback_ata: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Pay `amount` of an approved proposal to its recipient and mark it executed.
+/// USDC proposals draw on the vault's own account and budgets, others on their
+/// mint config; budgets count what the recipient actually gets.
+/// Returns the amount received and the fee.
+pub fn pay_proposal(
+    accounts: ProposalPayout,
+    proposal: &mut Proposal,
+    amount: u64,
+    now: i64,
+) -> Result<(u64, u64)> {
+    let ProposalPayout {
+        vault,
+        mint_config,
+        mint,
+        vault_token_ata,
+        recipient_ata,
+        protocol_config,
+        staker_reward_ata,
+        buyback_ata,
+        token_buyback_ata,
+        token_program,
+    } = accounts;
+
+    let received = amount_received(mint, amount)?;
+    let is_usdc = proposal.mint == vault.usdc_mint;
+    if is_usdc {
+        require_keys_eq!(vault_token_ata.key(), vault.vault_usdc_ata, VaultError::InvalidTokenAccount);
+        let vault: &mut Vault = vault;
+        record_budget_spend(&mut vault.daily_budget, &mut vault.weekly_budget, received, now)?;
+    } else {
+        let mint_config = mint_config.ok_or(VaultError::MintNotConfigured)?;
+        require_keys_eq!(vault_token_ata.key(), mint_config.vault_ata, VaultError::InvalidTokenAccount);
+        record_budget_spend(
+            &mut mint_config.daily_budget,
+            &mut mint_config.weekly_budget,
+            received,
+            now,
+        )?;
+    }
+
+    // Execute transfer
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let vault_info = vault.to_account_info();
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: vault_token_ata.to_account_info(),
+            mint: mint.to_account_info(),
+            to: recipient_ata,
+            authority: vault_info.clone(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+    // Calculate and transfer fee; non-USDC fees go entirely to buyback
+    let (staker_reward_ata, buyback_ata) = if is_usdc {
+        (Some(staker_reward_ata), buyback_ata)
+    } else {
+        (None, token_buyback_ata.ok_or(VaultError::InvalidTokenAccount)?)
+    };
+    let fee = calculate_and_transfer_fee(
+        received,
+        protocol_config.fee_bps,
+        protocol_config.total_staked,
+        mint,
+        vault_token_ata,
+        staker_reward_ata,
+        buyback_ata,
+        &vault_info,
+        token_program,
+        signer_seeds,
+    )?;
+
+    proposal.executed = true;
+    proposal.approved_amount = Some(amount);
+
+    if is_usdc {
+        record_usdc_outflow(vault, amount, fee)?;
+    }
+
+    Ok((received, fee))
+}
+
+/// Change a delay on the vault. Increases apply at once; a decrease only applies
+/// once the delay in force has passed. Returns when `delay` takes effect.
+pub fn change_delay(
+    current: &mut i64,
+    pending: &mut Option<PendingDelay>,
+    delay: i64,
+    now: i64,
+) -> Result<i64> {
+    require!(delay >= 0, VaultError::InvalidDelay);
+
+    // Settle an earlier decrease first so the comparison uses the delay in force
+    *current = PendingDelay::resolve(*current, *pending, now);
+    *pending = None;
+    if delay >= *current {
+        *current = delay;
+        return Ok(now);
+    }
+
+    let effective_at = now.checked_add(*current).ok_or(VaultError::Overflow)?;
+    *pending = Some(PendingDelay { delay, effective_at });
+    Ok(effective_at)
+}
+
+/// Whether `new` allows more than `old`, where zero means no limit.
+fn is_cap_raised(old: u64, new: u64) -> bool {
+    old != 0 && (new == 0 || new > old)
+}
+
+/// Whether replacing the entry's limits with `limits` lets the agent send more.
+pub fn loosens_whitelist_limits(entry: &WhitelistEntry, limits: &WhitelistLimits) -> bool {
+    is_cap_raised(entry.max_per_tx, limits.max_per_tx)
+        || is_cap_raised(entry.period_budget.limit, limits.max_per_period)
+        || (entry.period_budget.limit != 0 && limits.period_seconds < entry.period_budget.period)
+}
+
+/// Validate whitelist limits and write them onto the entry.
+/// Changing the period restarts the entry's spend window.
+pub fn apply_whitelist_limits(entry: &mut WhitelistEntry, limits: &WhitelistLimits) -> Result<()> {
+    require!(
+        limits.period_seconds >= 0
+            && (limits.max_per_period == 0 || limits.period_seconds > 0)
+            && (limits.max_per_tx == 0
+                || limits.max_per_period == 0
+                || limits.max_per_tx <= limits.max_per_period),
+        VaultError::InvalidWhitelistLimits
+    );
+
+    entry.max_per_tx = limits.max_per_tx;
+    if entry.period_budget.period != limits.period_seconds {
+        entry.period_budget = SpendWindow::new(limits.max_per_period, limits.period_seconds);
+    } else {
+        entry.period_budget.limit = limits.max_per_period;
+    }
+    Ok(())
+}
+
+/// Add a guardian's vote to a recovery request. Votes from keys no longer in the
+/// guardian set are dropped, and the veto timelock starts once the threshold is met.
+pub fn record_recovery_vote(
+    vault: &Vault,
+    request: &mut RecoveryRequest,
+    guardian: Pubkey,
+    now: i64,
+) -> Result<()> {
+    request.approvals.retain(|g| vault.guardians.contains(g));
+    require!(
+        !request.approvals.contains(&guardian),
+        VaultError::AlreadyApproved
+    );
+    request.approvals.push(guardian);
+
+    if request.executable_at.is_none()
+        && request.approvals.len() >= vault.guardian_threshold as usize
+    {
+        request.executable_at = Some(
+            now.checked_add(vault.recovery_delay)
+                .ok_or(VaultError::Overflow)?,
+        );
+    }
+    Ok(())
+}
+
 /// Lazy Synthetix-style reward update.
 /// Call before any stake/unstake/claim to bring accounting up to date.
 pub fn update_rewards(
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct AcceptHumanTransfer<'info> {
+    pub new_human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.pending_human.is_some() @ VaultError::NoPendingHumanTransfer,
+        constraint = vault.pending_human == Some(new_human.key()) @ VaultError::OnlyPendingHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<AcceptHumanTransfer>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let old_human = vault.human;
+    vault.human = ctx.accounts.new_human.key();
+    vault.pending_human = None;
+
+    emit!(HumanTransferred {
+        vault: vault.key(),
+        old_human,
+        new_human: vault.human,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(agent: Pubkey)]
+pub struct AddAgent<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+        constraint = vault.agent != agent @ VaultError::AlreadyPrimaryAgent,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = human,
+        space = 8 + AgentRole::INIT_SPACE,
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent.as_ref()],
+        bump,
+    )]
+    pub agent_role: Account<'info, AgentRole>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<AddAgent>,
+    agent: Pubkey,
+    tier1_max: u64,
+    tier2_max: u64,
+    can_propose: bool,
+) -> Result<()> {
+    require!(tier1_max <= tier2_max, VaultError::InvalidThresholds);
+
+    let vault = &mut ctx.accounts.vault;
+    vault.agent_roles = vault.agent_roles.checked_add(1).ok_or(VaultError::Overflow)?;
+
+    let role = &mut ctx.accounts.agent_role;
+    role.vault = ctx.accounts.vault.key();
+    role.agent = agent;
+    role.tier1_max = tier1_max;
+    role.tier2_max = tier2_max;
+    role.paused = false;
+    role.can_propose = can_propose;
+    role.added_at = Clock::get()?.unix_timestamp;
+    role.bump = ctx.bumps.agent_role;
+
+    emit!(AgentAdded {
+        vault: role.vault,
+        agent,
+        tier1_max,
+        tier2_max,
+        can_propose,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct AddApprover<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<AddApprover>, approver: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    require!(!vault.approvers.contains(&approver), VaultError::AlreadyApprover);
+    require!(vault.approvers.len() < MAX_APPROVERS, VaultError::TooManyApprovers);
+
+    vault.approvers.push(approver);
+    // The first approver starts the set off as 1-of-1
+    if vault.approval_threshold == 0 {
+        vault.approval_threshold = 1;
+    }
+
+    emit!(ApproverAdded {
+        vault: vault.key(),
+        approver,
+        approval_threshold: vault.approval_threshold,
+    });
+
+    Ok(())
+}
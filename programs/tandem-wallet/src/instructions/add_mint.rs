@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct AddMint<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// USDC is configured on the vault itself and can't get a separate config
+    #[account(constraint = mint.key() != vault.usdc_mint @ VaultError::CannotConfigureUsdcMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = human,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = human,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<AddMint>, tier1_max: u64, tier2_max: u64) -> Result<()> {
+    require!(tier1_max <= tier2_max, VaultError::InvalidThresholds);
+
+    let vault = &mut ctx.accounts.vault;
+    vault.mint_configs = vault.mint_configs.checked_add(1).ok_or(VaultError::Overflow)?;
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.vault = ctx.accounts.vault.key();
+    mint_config.mint = ctx.accounts.mint.key();
+    mint_config.vault_ata = ctx.accounts.vault_token_ata.key();
+    mint_config.tier1_max = tier1_max;
+    mint_config.tier2_max = tier2_max;
+    mint_config.daily_budget = SpendWindow::new(0, SpendWindow::DAY_SECONDS);
+    mint_config.weekly_budget = SpendWindow::new(0, SpendWindow::WEEK_SECONDS);
+    mint_config.bump = ctx.bumps.mint_config;
+
+    emit!(MintAdded {
+        vault: mint_config.vault,
+        mint: mint_config.mint,
+        vault_ata: mint_config.vault_ata,
+        tier1_max,
+        tier2_max,
+    });
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::helpers;
 
 #[derive(Accounts)]
 #[instruction(address: Pubkey)]
@@ -10,7 +11,8 @@ pub struct AddWhitelist<'info> {
     pub human: Signer<'info>,
 
     #[account(
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
         constraint = vault.human == human.key() @ VaultError::OnlyHuman,
     )]
@@ -28,16 +30,40 @@ pub struct AddWhitelist<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<AddWhitelist>, address: Pubkey) -> Result<()> {
+pub fn handler(
+    ctx: Context<AddWhitelist>,
+    address: Pubkey,
+    limits: WhitelistLimits,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let active_from = now
+        .checked_add(ctx.accounts.vault.whitelist_delay_at(now))
+        .ok_or(VaultError::Overflow)?;
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > active_from, VaultError::InvalidWhitelistExpiry);
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.whitelist_entries = vault.whitelist_entries.checked_add(1).ok_or(VaultError::Overflow)?;
+
     let wl = &mut ctx.accounts.whitelist_entry;
     wl.vault = ctx.accounts.vault.key();
     wl.address = address;
-    wl.added_at = Clock::get()?.unix_timestamp;
+    wl.added_at = now;
+    wl.active_from = active_from;
+    wl.expires_at = expires_at;
     wl.bump = ctx.bumps.whitelist_entry;
+    helpers::apply_whitelist_limits(wl, &limits)?;
 
     emit!(WhitelistAdded {
         vault: ctx.accounts.vault.key(),
         address,
+        max_per_tx: limits.max_per_tx,
+        max_per_period: limits.max_per_period,
+        period_seconds: limits.period_seconds,
+        active_from,
+        expires_at,
     });
 
     Ok(())
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Lets the proposing agent change a pending proposal. Each amendment bumps the
+/// revision and drops votes cast on earlier revisions.
+#[derive(Accounts)]
+pub struct AmendProposal<'info> {
+    pub agent: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = !vault.paused @ VaultError::VaultPaused,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        mut,
+        seeds = [
+            Proposal::SEED_PREFIX,
+            vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.vault == vault.key(),
+        constraint = proposal.proposer == agent.key() @ VaultError::OnlyProposer,
+        constraint = !proposal.executed @ VaultError::ProposalAlreadyExecuted,
+        constraint = !proposal.cancelled @ VaultError::ProposalAlreadyCancelled,
+        constraint = proposal.approved_at.is_none() @ VaultError::ProposalScheduled,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: New recipient wallet; omit to keep the current one
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: New recipient's token account, required with `recipient` (checked in handler)
+    pub recipient_ata: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<AmendProposal>, amount: Option<u64>, memo: Option<String>) -> Result<()> {
+    let limits = helpers::agent_limits(
+        &ctx.accounts.vault,
+        &ctx.accounts.agent.key(),
+        ctx.accounts.agent_role.as_deref(),
+    )?;
+    require!(limits.can_propose, VaultError::AgentCannotPropose);
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.is_expired(now), VaultError::ProposalExpired);
+
+    if let Some(amount) = amount {
+        require!(amount > 0, VaultError::ZeroAmount);
+        proposal.amount = amount;
+    }
+    if let Some(memo) = memo {
+        require!(memo.len() <= MAX_MEMO_LEN, VaultError::MemoTooLong);
+        proposal.memo = memo;
+    }
+    if let Some(ref recipient) = ctx.accounts.recipient {
+        let recipient_ata = ctx
+            .accounts
+            .recipient_ata
+            .as_ref()
+            .ok_or(VaultError::InvalidTokenAccount)?;
+        helpers::check_recipient_token_account(recipient_ata, &recipient.key(), &proposal.mint)?;
+        proposal.recipient = recipient.key();
+        proposal.recipient_ata = recipient_ata.key();
+    }
+
+    // The amended proposal gets a fresh veto period if it's (still) in the optimistic band
+    let executable_at = helpers::optimistic_executable_at(
+        &ctx.accounts.vault,
+        &limits,
+        &proposal.mint,
+        proposal.amount,
+        now,
+    )?;
+    proposal.executable_at = executable_at;
+    proposal.optimistic = executable_at.is_some();
+    // A fresh veto period needs a fresh lifetime, or the proposal could expire first
+    if let (Some(executable_at), Some(expires_at)) = (executable_at, proposal.expires_at) {
+        if executable_at >= expires_at {
+            let proposal_ttl = ctx.accounts.vault.proposal_ttl;
+            proposal.expires_at = if proposal_ttl > 0 {
+                Some(now.checked_add(proposal_ttl).ok_or(VaultError::Overflow)?)
+            } else {
+                None
+            };
+        }
+    }
+
+    proposal.revision = proposal.revision.checked_add(1).ok_or(VaultError::Overflow)?;
+    proposal.approvals.clear();
+    proposal.approval_amounts.clear();
+
+    emit!(ProposalAmended {
+        vault: ctx.accounts.vault.key(),
+        proposal_id: proposal.proposal_id,
+        revision: proposal.revision,
+        recipient: proposal.recipient,
+        amount: proposal.amount,
+        memo: proposal.memo.clone(),
+        expires_at: proposal.expires_at,
+        executable_at: proposal.executable_at,
+    });
+
+    Ok(())
+}
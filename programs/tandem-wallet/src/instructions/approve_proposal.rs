@@ -1,18 +1,25 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
 use crate::helpers;
 
+/// Votes for a proposal, naming the revision and requested amount the approver
+/// saw; `recipient_ata` already pins the recipient and mint. Proposal accounts
+/// can be closed and recreated under the same ID, so the vote fails rather than
+/// land on a different payment.
 #[derive(Accounts)]
 pub struct ApproveProposal<'info> {
-    pub human: Signer<'info>,
+    #[account(mut)]
+    pub approver: Signer<'info>,
 
     #[account(
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
-        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+        constraint = vault.is_approver(&approver.key()) @ VaultError::OnlyApprover,
         constraint = !vault.paused @ VaultError::VaultPaused,
     )]
     pub vault: Account<'info, Vault>,
@@ -28,92 +35,175 @@ pub struct ApproveProposal<'info> {
         constraint = proposal.vault == vault.key(),
         constraint = !proposal.executed @ VaultError::ProposalAlreadyExecuted,
         constraint = !proposal.cancelled @ VaultError::ProposalAlreadyCancelled,
+        constraint = proposal.approved_at.is_none() @ VaultError::ProposalScheduled,
     )]
     pub proposal: Account<'info, Proposal>,
 
+    #[account(
+        constraint = mint.key() == proposal.mint @ VaultError::InvalidTokenAccount,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Config for the proposal's mint; required unless it pays out in USDC.
     #[account(
         mut,
-        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), proposal.mint.as_ref()],
+        bump = mint_config.bump,
     )]
-    pub vault_usdc_ata: Account<'info, TokenAccount>,
+    pub mint_config: Option<Account<'info, MintConfig>>,
 
+    /// The vault's token account for the proposal's mint (checked in handler)
+    #[account(mut)]
+    pub vault_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Checked against the proposal; may be created in this instruction
     #[account(
         mut,
         constraint = recipient_ata.key() == proposal.recipient_ata,
     )]
-    pub recipient_ata: Account<'info, TokenAccount>,
+    pub recipient_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet. Pass it to create `recipient_ata` as their associated
+    /// token account on execution if it doesn't exist yet; the approver pays the rent.
+    #[account(
+        constraint = recipient.key() == proposal.recipient,
+    )]
+    pub recipient: Option<UncheckedAccount<'info>>,
 
     /// Protocol config for fee calculation
     #[account(
         seeds = [ProtocolConfig::SEED_PREFIX],
         bump = protocol_config.bump,
     )]
-    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
     /// Staker reward USDC ATA (receives 50% of fee)
     #[account(
         mut,
         constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
     )]
-    pub staker_reward_ata: Account<'info, TokenAccount>,
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Buyback USDC ATA (receives 50% of fee)
     #[account(
         mut,
         constraint = buyback_ata.key() == protocol_config.buyback_ata,
     )]
-    pub buyback_ata: Account<'info, TokenAccount>,
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback wallet's account for the proposal's mint; required unless it pays out in USDC
+    #[account(
+        mut,
+        constraint = token_buyback_ata.mint == proposal.mint @ VaultError::InvalidTokenAccount,
+        constraint = token_buyback_ata.owner == buyback_ata.owner @ VaultError::InvalidTokenAccount,
+    )]
+    pub token_buyback_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-pub fn handler(ctx: Context<ApproveProposal>) -> Result<()> {
+pub fn handler(
+    ctx: Context<ApproveProposal>,
+    revision: u32,
+    requested_amount: u64,
+    approved_amount: Option<u64>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.proposal.is_expired(now), VaultError::ProposalExpired);
+    require!(
+        ctx.accounts.proposal.revision == revision,
+        VaultError::ProposalRevisionMismatch
+    );
+    require!(
+        ctx.accounts.proposal.amount == requested_amount,
+        VaultError::ProposalAmountMismatch
+    );
+    let vote_amount = approved_amount.unwrap_or(requested_amount);
+    require!(
+        vote_amount > 0 && vote_amount <= requested_amount,
+        VaultError::InvalidApprovedAmount
+    );
+
+    // Record the vote, dropping votes from keys no longer in the approver set
+    let approver_key = ctx.accounts.approver.key();
     let vault = &ctx.accounts.vault;
     let proposal = &mut ctx.accounts.proposal;
+    let (approvals, threshold) = helpers::record_proposal_vote(vault, proposal, approver_key, vote_amount)?;
+    emit!(ProposalApprovalRecorded {
+        vault: vault.key(),
+        proposal_id: proposal.proposal_id,
+        approver: approver_key,
+        approvals: approvals as u8,
+        threshold: threshold as u8,
+    });
 
-    // Execute transfer
-    let human_key = vault.human;
-    let agent_key = vault.agent;
-    let bump = vault.bump;
-    let seeds = &[
-        Vault::SEED_PREFIX,
-        human_key.as_ref(),
-        agent_key.as_ref(),
-        &[bump],
-    ];
-    let signer_seeds = &[&seeds[..]];
-
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.vault_usdc_ata.to_account_info(),
-            to: ctx.accounts.recipient_ata.to_account_info(),
-            authority: ctx.accounts.vault.to_account_info(),
+    if approvals < threshold {
+        return Ok(());
+    }
+
+    // Pay no more than the most cautious approver agreed to
+    let amount = proposal.min_approved_amount().unwrap_or(vote_amount);
+
+    // With an execution delay, approval only schedules the payout
+    let execution_delay = ctx.accounts.vault.execution_delay_at(now);
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.approved_at = Some(now);
+    if execution_delay > 0 {
+        let executable_at = now.checked_add(execution_delay).ok_or(VaultError::Overflow)?;
+        proposal.executable_at = Some(executable_at);
+        proposal.approved_amount = Some(amount);
+
+        emit!(ProposalScheduled {
+            vault: ctx.accounts.vault.key(),
+            proposal_id: proposal.proposal_id,
+            approved_amount: amount,
+            executable_at,
+        });
+        return Ok(());
+    }
+
+    if let Some(ref recipient) = ctx.accounts.recipient {
+        helpers::create_ata_idempotent(
+            ctx.accounts.approver.to_account_info(),
+            ctx.accounts.recipient_ata.to_account_info(),
+            recipient.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.associated_token_program.to_account_info(),
+        )?;
+    }
+
+    let (received, fee) = helpers::pay_proposal(
+        helpers::ProposalPayout {
+            vault: &mut ctx.accounts.vault,
+            mint_config: ctx.accounts.mint_config.as_deref_mut(),
+            mint: &ctx.accounts.mint,
+            vault_token_ata: &ctx.accounts.vault_token_ata,
+            recipient_ata: ctx.accounts.recipient_ata.to_account_info(),
+            protocol_config: &ctx.accounts.protocol_config,
+            staker_reward_ata: &ctx.accounts.staker_reward_ata,
+            buyback_ata: &ctx.accounts.buyback_ata,
+            token_buyback_ata: ctx.accounts.token_buyback_ata.as_deref(),
+            token_program: &ctx.accounts.token_program,
         },
-        signer_seeds,
-    );
-    token::transfer(cpi_ctx, proposal.amount)?;
-
-    // Calculate and transfer fee
-    let fee = helpers::calculate_and_transfer_fee(
-        proposal.amount,
-        ctx.accounts.protocol_config.fee_bps,
-        ctx.accounts.protocol_config.total_staked,
-        &ctx.accounts.vault_usdc_ata,
-        &ctx.accounts.staker_reward_ata,
-        &ctx.accounts.buyback_ata,
-        &ctx.accounts.vault.to_account_info(),
-        &ctx.accounts.token_program,
-        signer_seeds,
+        &mut ctx.accounts.proposal,
+        amount,
+        now,
     )?;
 
-    proposal.executed = true;
-
+    let proposal = &ctx.accounts.proposal;
     emit!(ProposalApproved {
-        vault: vault.key(),
+        vault: ctx.accounts.vault.key(),
         proposal_id: proposal.proposal_id,
+        sequence: proposal.sequence,
+        mint: proposal.mint,
         recipient: proposal.recipient,
-        amount: proposal.amount,
+        requested_amount,
+        approved_amount: amount,
+        amount: received,
         fee,
     });
 
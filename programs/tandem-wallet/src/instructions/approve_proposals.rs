@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Maximum number of proposals in one `approve_proposals` or `cancel_proposals`.
+pub const MAX_BATCH_PROPOSALS: usize = 10;
+
+/// Votes for several USDC proposals at once. Remaining accounts come in pairs:
+/// the proposal, then its recipient token account. Each proposal is checked
+/// against its revision and the amount it requested, then paid (or scheduled)
+/// and charged its fee exactly as `approve_proposal` would. Proposals in other
+/// mints must be approved one at a time with `approve_proposal`.
+#[derive(Accounts)]
+pub struct ApproveProposals<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.is_approver(&approver.key()) @ VaultError::OnlyApprover,
+        constraint = !vault.paused @ VaultError::VaultPaused,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ApproveProposals<'info>>,
+    revisions: Vec<u32>,
+    requested_amounts: Vec<u64>,
+) -> Result<()> {
+    require!(
+        !revisions.is_empty()
+            && revisions.len() <= MAX_BATCH_PROPOSALS
+            && requested_amounts.len() == revisions.len()
+            && ctx.remaining_accounts.len() == revisions.len() * 2,
+        VaultError::InvalidProposalBatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let vault_key = ctx.accounts.vault.key();
+    let approver_key = ctx.accounts.approver.key();
+
+    let batch = ctx.remaining_accounts.chunks(2).zip(revisions.iter()).zip(requested_amounts.iter());
+    for ((accounts, revision), expected_amount) in batch {
+        let mut proposal = helpers::load_pending_proposal(&accounts[0], &vault_key)?;
+        require!(proposal.approved_at.is_none(), VaultError::ProposalScheduled);
+        require!(!proposal.is_expired(now), VaultError::ProposalExpired);
+        require!(proposal.revision == *revision, VaultError::ProposalRevisionMismatch);
+        require!(proposal.amount == *expected_amount, VaultError::ProposalAmountMismatch);
+        require_keys_eq!(proposal.mint, ctx.accounts.vault.usdc_mint, VaultError::BatchUsdcOnly);
+        require_keys_eq!(accounts[1].key(), proposal.recipient_ata, VaultError::InvalidTokenAccount);
+        require!(accounts[1].is_writable, VaultError::InvalidProposalBatch);
+
+        // Batch approvals always vote for the full requested amount
+        let requested_amount = proposal.amount;
+        let (approvals, threshold) =
+            helpers::record_proposal_vote(&ctx.accounts.vault, &mut proposal, approver_key, requested_amount)?;
+        emit!(ProposalApprovalRecorded {
+            vault: vault_key,
+            proposal_id: proposal.proposal_id,
+            approver: approver_key,
+            approvals: approvals as u8,
+            threshold: threshold as u8,
+        });
+
+        if approvals >= threshold {
+            let amount = proposal.min_approved_amount().unwrap_or(proposal.amount);
+            let execution_delay = ctx.accounts.vault.execution_delay_at(now);
+            proposal.approved_at = Some(now);
+
+            if execution_delay > 0 {
+                let executable_at = now.checked_add(execution_delay).ok_or(VaultError::Overflow)?;
+                proposal.executable_at = Some(executable_at);
+                proposal.approved_amount = Some(amount);
+                emit!(ProposalScheduled {
+                    vault: vault_key,
+                    proposal_id: proposal.proposal_id,
+                    approved_amount: amount,
+                    executable_at,
+                });
+            } else {
+                let (received, fee) = helpers::pay_proposal(
+                    helpers::ProposalPayout {
+                        vault: &mut ctx.accounts.vault,
+                        mint_config: None,
+                        mint: &ctx.accounts.usdc_mint,
+                        vault_token_ata: &ctx.accounts.vault_usdc_ata,
+                        recipient_ata: accounts[1].clone(),
+                        protocol_config: &ctx.accounts.protocol_config,
+                        staker_reward_ata: &ctx.accounts.staker_reward_ata,
+                        buyback_ata: &ctx.accounts.buyback_ata,
+                        token_buyback_ata: None,
+                        token_program: &ctx.accounts.token_program,
+                    },
+                    &mut proposal,
+                    amount,
+                    now,
+                )?;
+                emit!(ProposalApproved {
+                    vault: vault_key,
+                    proposal_id: proposal.proposal_id,
+                    sequence: proposal.sequence,
+                    mint: proposal.mint,
+                    recipient: proposal.recipient,
+                    requested_amount,
+                    approved_amount: amount,
+                    amount: received,
+                    fee,
+                });
+            }
+        }
+
+        // Persist now so a repeated proposal sees this vote
+        proposal.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
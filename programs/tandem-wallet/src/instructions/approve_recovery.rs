@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.guardians.contains(&guardian.key()) @ VaultError::OnlyGuardian,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [RecoveryRequest::SEED_PREFIX, vault.key().as_ref()],
+        bump = recovery_request.bump,
+        constraint = recovery_request.vault == vault.key(),
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+}
+
+pub fn handler(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = &ctx.accounts.vault;
+    let guardian = ctx.accounts.guardian.key();
+    let request = &mut ctx.accounts.recovery_request;
+    helpers::record_recovery_vote(vault, request, guardian, now)?;
+
+    emit!(RecoveryApproved {
+        vault: vault.key(),
+        guardian,
+        approvals: request.approvals.len() as u8,
+        threshold: vault.guardian_threshold,
+        executable_at: request.executable_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelHumanTransfer<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<CancelHumanTransfer>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let pending_human = vault
+        .pending_human
+        .take()
+        .ok_or(VaultError::NoPendingHumanTransfer)?;
+
+    emit!(HumanTransferCancelled {
+        vault: vault.key(),
+        pending_human,
+    });
+
+    Ok(())
+}
@@ -2,15 +2,19 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::helpers;
 
+/// The human can cancel any pending proposal. Guardians can cancel one only
+/// while it waits to become executable: approved under an execution delay, or
+/// optimistic and still inside its veto period. The reason and note are stored
+/// on the proposal so the agent can read back why it was rejected.
 #[derive(Accounts)]
 pub struct CancelProposal<'info> {
-    pub human: Signer<'info>,
+    pub signer: Signer<'info>,
 
     #[account(
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
-        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
     )]
     pub vault: Account<'info, Vault>,
 
@@ -29,13 +33,25 @@ pub struct CancelProposal<'info> {
     pub proposal: Account<'info, Proposal>,
 }
 
-pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
+pub fn handler(ctx: Context<CancelProposal>, reason: CancelReason, note: String) -> Result<()> {
+    require!(note.len() <= MAX_CANCEL_NOTE_LEN, VaultError::MemoTooLong);
+
+    let now = Clock::get()?.unix_timestamp;
+    let signer_key = ctx.accounts.signer.key();
     let proposal = &mut ctx.accounts.proposal;
+    helpers::check_proposal_canceller(&ctx.accounts.vault, proposal, &signer_key, now)?;
+
     proposal.cancelled = true;
+    proposal.cancel_reason = Some(reason);
+    proposal.cancel_note = note.clone();
 
     emit!(ProposalCancelled {
         vault: ctx.accounts.vault.key(),
         proposal_id: proposal.proposal_id,
+        sequence: proposal.sequence,
+        cancelled_by: signer_key,
+        reason,
+        note,
     });
 
     Ok(())
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+use super::approve_proposals::MAX_BATCH_PROPOSALS;
+
+/// Cancels several proposals, passed as remaining accounts, under the same
+/// rules as `cancel_proposal`. All of them get the same reason and note.
+#[derive(Accounts)]
+pub struct CancelProposals<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelProposals<'info>>,
+    reason: CancelReason,
+    note: String,
+) -> Result<()> {
+    require!(note.len() <= MAX_CANCEL_NOTE_LEN, VaultError::MemoTooLong);
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_BATCH_PROPOSALS,
+        VaultError::InvalidProposalBatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let vault_key = ctx.accounts.vault.key();
+    let signer_key = ctx.accounts.signer.key();
+
+    for info in ctx.remaining_accounts.iter() {
+        let mut proposal = helpers::load_pending_proposal(info, &vault_key)?;
+        helpers::check_proposal_canceller(&ctx.accounts.vault, &proposal, &signer_key, now)?;
+        proposal.cancelled = true;
+        proposal.cancel_reason = Some(reason);
+        proposal.cancel_note = note.clone();
+
+        emit!(ProposalCancelled {
+            vault: vault_key,
+            proposal_id: proposal.proposal_id,
+            sequence: proposal.sequence,
+            cancelled_by: signer_key,
+            reason,
+            note: note.clone(),
+        });
+
+        proposal.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
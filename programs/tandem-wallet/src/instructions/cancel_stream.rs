@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Settles and closes a stream: anything vested but not yet withdrawn goes to
+/// the recipient with its fee taken from the stream's reserve, whatever is left
+/// in the stream (unvested funds and unused reserve) returns to `vault_usdc_ata`,
+/// and the rent of both stream accounts returns to the creator.
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    /// The stream creator or the vault's human
+    pub signer: Signer<'info>,
+
+    /// CHECK: Receives the stream's rent; must match stream.creator
+    #[account(
+        mut,
+        constraint = creator.key() == stream.creator,
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [Stream::SEED_PREFIX, vault.key().as_ref(), stream.stream_id.to_le_bytes().as_ref()],
+        bump = stream.bump,
+        constraint = signer.key() == stream.creator || signer.key() == vault.human @ VaultError::OnlyStreamCreator,
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), signer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = stream_ata.key() == stream.stream_ata,
+    )]
+    pub stream_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_ata.key() == stream.recipient_ata,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<CancelStream>) -> Result<()> {
+    // The stored creator key may have been removed as an agent since
+    if ctx.accounts.signer.key() != ctx.accounts.vault.human {
+        helpers::agent_limits(
+            &ctx.accounts.vault,
+            &ctx.accounts.signer.key(),
+            ctx.accounts.agent_role.as_deref(),
+        )?;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let stream = &ctx.accounts.stream;
+    let paid_out = stream.vested(now).saturating_sub(stream.withdrawn);
+
+    let vault_key = ctx.accounts.vault.key();
+    let stream_id = stream.stream_id.to_le_bytes();
+    let stream_bump = stream.bump;
+    let stream_seeds = &[
+        Stream::SEED_PREFIX,
+        vault_key.as_ref(),
+        stream_id.as_ref(),
+        &[stream_bump],
+    ];
+    let stream_signer = &[&stream_seeds[..]];
+
+    // Vested remainder to the recipient, fee from the stream's reserve
+    let mut fee = 0;
+    if paid_out > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.stream_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.recipient_ata.to_account_info(),
+                authority: ctx.accounts.stream.to_account_info(),
+            },
+            stream_signer,
+        );
+        token_interface::transfer_checked(cpi_ctx, paid_out, ctx.accounts.usdc_mint.decimals)?;
+
+        fee = helpers::calculate_and_transfer_fee(
+            helpers::amount_received(&ctx.accounts.usdc_mint, paid_out)?,
+            ctx.accounts.stream.fee_bps,
+            ctx.accounts.protocol_config.total_staked,
+            &ctx.accounts.usdc_mint,
+            &ctx.accounts.stream_ata,
+            Some(&ctx.accounts.staker_reward_ata),
+            &ctx.accounts.buyback_ata,
+            &ctx.accounts.stream.to_account_info(),
+            &ctx.accounts.token_program,
+            stream_signer,
+        )?;
+    }
+
+    // Everything left, unvested funds and unused reserve, back to the vault
+    ctx.accounts.stream_ata.reload()?;
+    let returned = ctx.accounts.stream_ata.amount;
+    let mut returned_received = 0;
+    if returned > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.stream_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.vault_usdc_ata.to_account_info(),
+                authority: ctx.accounts.stream.to_account_info(),
+            },
+            stream_signer,
+        );
+        token_interface::transfer_checked(cpi_ctx, returned, ctx.accounts.usdc_mint.decimals)?;
+        returned_received = helpers::amount_received(&ctx.accounts.usdc_mint, returned)?;
+    }
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.stream_ata.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.stream.to_account_info(),
+        },
+        stream_signer,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    // The reserve was counted as sent: what was charged now counts as fees,
+    // and what came back is no longer counted at all
+    let vault: &mut Vault = &mut ctx.accounts.vault;
+    vault.total_sent = vault
+        .total_sent
+        .checked_sub(fee)
+        .and_then(|sent| sent.checked_sub(returned_received))
+        .ok_or(VaultError::Overflow)?;
+    helpers::record_usdc_outflow(vault, 0, fee)?;
+    vault.open_streams = vault.open_streams.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+    emit!(StreamCancelled {
+        vault: vault_key,
+        stream_id: ctx.accounts.stream.stream_id,
+        cancelled_by: ctx.accounts.signer.key(),
+        paid_out,
+        returned,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = human,
+        seeds = [
+            Subscription::SEED_PREFIX,
+            vault.key().as_ref(),
+            subscription.subscription_id.to_le_bytes().as_ref(),
+        ],
+        bump = subscription.bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+}
+
+pub fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.open_subscriptions = vault
+        .open_subscriptions
+        .checked_sub(1)
+        .ok_or(VaultError::Overflow)?;
+
+    emit!(SubscriptionCancelled {
+        vault: ctx.accounts.vault.key(),
+        subscription_id: ctx.accounts.subscription.subscription_id,
+        payments_made: ctx.accounts.subscription.payments_made,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelWhitelist<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [WhitelistEntry::SEED_PREFIX, vault.key().as_ref(), whitelist_entry.address.as_ref()],
+        bump = whitelist_entry.bump,
+        constraint = whitelist_entry.vault == vault.key(),
+        close = human,
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+}
+
+pub fn handler(ctx: Context<CancelWhitelist>) -> Result<()> {
+    // Only entries still inside their activation delay can be cancelled
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now < ctx.accounts.whitelist_entry.active_from,
+        VaultError::WhitelistEntryActive
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    vault.whitelist_entries = vault.whitelist_entries.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+    emit!(WhitelistCancelled {
+        vault: ctx.accounts.vault.key(),
+        address: ctx.accounts.whitelist_entry.address,
+    });
+
+    Ok(())
+}
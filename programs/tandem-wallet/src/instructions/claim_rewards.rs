@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
@@ -29,7 +29,7 @@ pub struct ClaimRewards<'info> {
         mut,
         constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
     )]
-    pub staker_reward_ata: Account<'info, TokenAccount>,
+    pub staker_reward_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// Staker's personal USDC ATA (destination for claimed rewards)
     #[account(
@@ -37,9 +37,14 @@ pub struct ClaimRewards<'info> {
         constraint = staker_usdc_ata.mint == protocol_config.usdc_mint,
         constraint = staker_usdc_ata.owner == staker.key(),
     )]
-    pub staker_usdc_ata: Account<'info, TokenAccount>,
+    pub staker_usdc_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        constraint = usdc_mint.key() == protocol_config.usdc_mint,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
@@ -50,6 +55,8 @@ pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
     let config_info = ctx.accounts.protocol_config.to_account_info();
     let token_info = ctx.accounts.token_program.to_account_info();
     let from_info = ctx.accounts.staker_reward_ata.to_account_info();
+    let mint_info = ctx.accounts.usdc_mint.to_account_info();
+    let decimals = ctx.accounts.usdc_mint.decimals;
     let to_info = ctx.accounts.staker_usdc_ata.to_account_info();
 
     let config = &mut ctx.accounts.protocol_config;
@@ -67,14 +74,15 @@ pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
 
     let cpi_ctx = CpiContext::new_with_signer(
         token_info,
-        Transfer {
+        TransferChecked {
             from: from_info,
+            mint: mint_info,
             to: to_info,
             authority: config_info,
         },
         signer_seeds,
     );
-    token::transfer(cpi_ctx, rewards)?;
+    token_interface::transfer_checked(cpi_ctx, rewards, decimals)?;
 
     // Update accounting
     stake_account.rewards_owed = 0;
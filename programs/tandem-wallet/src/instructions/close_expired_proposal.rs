@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+#[derive(Accounts)]
+pub struct CloseExpiredProposal<'info> {
+    /// Anyone can clean up an expired proposal.
+    pub closer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            Proposal::SEED_PREFIX,
+            vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.vault == vault.key(),
+        constraint = !proposal.executed @ VaultError::ProposalAlreadyExecuted,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The proposer's ledger; required for nonce-keyed proposals.
+    #[account(
+        mut,
+        seeds = [AgentLedger::SEED_PREFIX, vault.key().as_ref(), proposal.proposer.as_ref()],
+        bump = agent_ledger.bump,
+    )]
+    pub agent_ledger: Option<Account<'info, AgentLedger>>,
+
+    /// CHECK: Receives the rent; must be the agent that paid for the proposal
+    #[account(
+        mut,
+        constraint = proposer.key() == proposal.proposer,
+    )]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseExpiredProposal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &ctx.accounts.proposal;
+    require!(proposal.is_expired(now), VaultError::ProposalNotExpired);
+    helpers::record_proposal_closed(
+        &mut ctx.accounts.vault,
+        ctx.accounts.agent_ledger.as_deref_mut(),
+        proposal,
+    )?;
+
+    emit!(ExpiredProposalClosed {
+        vault: ctx.accounts.vault.key(),
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        closer: ctx.accounts.closer.key(),
+    });
+
+    Ok(())
+}
@@ -1,19 +1,26 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::helpers;
 
 #[derive(Accounts)]
 pub struct CloseProposal<'info> {
-    #[account(mut)]
     pub agent: Signer<'info>,
 
     #[account(
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
-        constraint = vault.agent == agent.key() @ VaultError::OnlyAgent,
     )]
     pub vault: Account<'info, Vault>,
 
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
     #[account(
         mut,
         seeds = [
@@ -24,11 +31,37 @@ pub struct CloseProposal<'info> {
         bump = proposal.bump,
         constraint = proposal.vault == vault.key(),
         constraint = proposal.executed || proposal.cancelled @ VaultError::ProposalAlreadyExecuted,
-        close = agent,
+        close = proposer,
     )]
     pub proposal: Account<'info, Proposal>,
+
+    /// The proposer's ledger; required for nonce-keyed proposals.
+    #[account(
+        mut,
+        seeds = [AgentLedger::SEED_PREFIX, vault.key().as_ref(), proposal.proposer.as_ref()],
+        bump = agent_ledger.bump,
+    )]
+    pub agent_ledger: Option<Account<'info, AgentLedger>>,
+
+    /// CHECK: Receives the rent; must be the agent that paid for the proposal
+    #[account(
+        mut,
+        constraint = proposer.key() == proposal.proposer,
+    )]
+    pub proposer: UncheckedAccount<'info>,
 }
 
-pub fn handler(_ctx: Context<CloseProposal>) -> Result<()> {
+pub fn handler(ctx: Context<CloseProposal>) -> Result<()> {
+    // Any registered agent can clean up; rent always goes back to the proposer
+    helpers::agent_limits(
+        &ctx.accounts.vault,
+        &ctx.accounts.agent.key(),
+        ctx.accounts.agent_role.as_deref(),
+    )?;
+    helpers::record_proposal_closed(
+        &mut ctx.accounts.vault,
+        ctx.accounts.agent_ledger.as_deref_mut(),
+        &ctx.accounts.proposal,
+    )?;
     Ok(())
 }
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+/// Sweeps the vault's USDC to the human and closes the vault, its USDC account
+/// and every whitelist entry, proposal, agent role and agent ledger, all passed
+/// as remaining accounts. Subscriptions, streams and escrows pay out of or back
+/// into the vault, so they must be settled first; other mints must be emptied
+/// and removed with `remove_mint`, and a pending recovery vetoed.
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        close = human,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+        constraint = !vault.has_open_accounts() @ VaultError::VaultHasOpenAccounts,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: The vault's recovery request address; must not hold a pending request
+    #[account(
+        seeds = [RecoveryRequest::SEED_PREFIX, vault.key().as_ref()],
+        bump,
+        constraint = recovery_request.data_is_empty() @ VaultError::VaultHasOpenAccounts,
+    )]
+    pub recovery_request: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Human's own USDC token account, receives the remaining balance
+    #[account(
+        mut,
+        constraint = human_usdc_ata.owner == human.key() @ VaultError::InvalidTokenAccount,
+        constraint = human_usdc_ata.mint == vault.usdc_mint @ VaultError::InvalidTokenAccount,
+    )]
+    pub human_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseVault<'info>>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let vault_key = vault.key();
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Sweep whatever is left, fee-free
+    let swept = ctx.accounts.vault_usdc_ata.amount;
+    if swept > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.human_usdc_ata.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, swept, ctx.accounts.usdc_mint.decimals)?;
+    }
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_usdc_ata.to_account_info(),
+            destination: ctx.accounts.human.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    // Close leftover whitelist entries, proposals, agent roles and ledgers, rent to the human
+    let human_info = ctx.accounts.human.to_account_info();
+    let mut whitelist_entries: u32 = 0;
+    let mut proposals: u32 = 0;
+    let mut sequential_proposals: u32 = 0;
+    let mut agent_roles: u32 = 0;
+    // Open nonce-keyed proposals per proposer: (agent, on its ledger, closed here)
+    let mut ledgers: Vec<(Pubkey, u32, u32)> = Vec::new();
+    let mut nonce_proposers: Vec<Pubkey> = Vec::new();
+
+    for info in ctx.remaining_accounts.iter() {
+        if let Ok(entry) = Account::<WhitelistEntry>::try_from(info) {
+            require_keys_eq!(entry.vault, vault_key, VaultError::NotVaultAccount);
+            entry.close(human_info.clone())?;
+            whitelist_entries += 1;
+        } else if let Ok(proposal) = Account::<Proposal>::try_from(info) {
+            require_keys_eq!(proposal.vault, vault_key, VaultError::NotVaultAccount);
+            if proposal.is_nonce_keyed() {
+                nonce_proposers.push(proposal.proposer);
+            } else {
+                sequential_proposals += 1;
+            }
+            proposal.close(human_info.clone())?;
+            proposals += 1;
+        } else if let Ok(role) = Account::<AgentRole>::try_from(info) {
+            require_keys_eq!(role.vault, vault_key, VaultError::NotVaultAccount);
+            role.close(human_info.clone())?;
+            agent_roles += 1;
+        } else if let Ok(ledger) = Account::<AgentLedger>::try_from(info) {
+            require_keys_eq!(ledger.vault, vault_key, VaultError::NotVaultAccount);
+            ledgers.push((ledger.agent, ledger.open_proposals, 0));
+            ledger.close(human_info.clone())?;
+        } else {
+            return err!(VaultError::NotVaultAccount);
+        }
+    }
+
+    // Every child account must go, or it would come back to life if the vault is recreated
+    for proposer in nonce_proposers {
+        let ledger = ledgers
+            .iter_mut()
+            .find(|(agent, _, _)| *agent == proposer)
+            .ok_or(VaultError::VaultHasOpenAccounts)?;
+        ledger.2 += 1;
+    }
+    let vault = &ctx.accounts.vault;
+    require!(
+        agent_roles == vault.agent_roles
+            && whitelist_entries == vault.whitelist_entries
+            && sequential_proposals == vault.open_proposals
+            && ledgers.len() == vault.agent_ledgers as usize
+            && ledgers.iter().all(|(_, open, closed)| open == closed),
+        VaultError::VaultHasOpenAccounts
+    );
+
+    emit!(VaultClosed {
+        vault: vault_key,
+        human: ctx.accounts.human.key(),
+        swept,
+        whitelist_entries,
+        proposals,
+        agent_roles,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Locks USDC from the vault in a new escrow until it is released to the payee
+/// or refunded. Agents are held to their tiers and budgets as for a direct send.
+/// The protocol fee for the release is locked in the escrow alongside the amount.
+#[derive(Accounts)]
+pub struct CreateEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// Role account when the payer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), payer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Payee wallet address
+    pub payee: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = payee_ata.mint == vault.usdc_mint @ VaultError::InvalidTokenAccount,
+        constraint = payee_ata.owner == payee.key() @ VaultError::InvalidTokenAccount,
+    )]
+    pub payee_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [
+            Escrow::SEED_PREFIX,
+            vault.key().as_ref(),
+            vault.escrow_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// The escrow's USDC account, owned by the escrow PDA
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config for the fee to reserve
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(
+    ctx: Context<CreateEscrow>,
+    amount: u64,
+    deadline: i64,
+    arbiter: Option<Pubkey>,
+    is_emergency: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(amount > 0, VaultError::ZeroAmount);
+    require!(deadline > now, VaultError::InvalidSchedule);
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    let vault = &ctx.accounts.vault;
+    let payer_key = ctx.accounts.payer.key();
+    let is_human = payer_key == vault.human;
+    let is_agent = payer_key == vault.agent || ctx.accounts.agent_role.is_some();
+
+    // Must be human or a registered agent
+    require!(is_human || is_agent, VaultError::OnlyAgentOrHuman);
+
+    let mut tier: u8 = 0;
+    if !is_human {
+        require!(!vault.paused, VaultError::VaultPaused);
+        let limits = helpers::agent_limits(vault, &payer_key, ctx.accounts.agent_role.as_deref())?;
+        tier = helpers::agent_tier(received, &limits, is_emergency)?;
+
+        let vault: &mut Vault = &mut ctx.accounts.vault;
+        helpers::record_budget_spend(&mut vault.daily_budget, &mut vault.weekly_budget, received, now)?;
+    }
+
+    // Fund the escrow, fee included, using vault PDA as signer
+    let fee_bps = ctx.accounts.protocol_config.fee_bps;
+    let funded = helpers::protocol_fee(received, fee_bps)?
+        .checked_add(amount)
+        .ok_or(VaultError::Overflow)?;
+    let fee_reserve = helpers::amount_received(&ctx.accounts.usdc_mint, funded)?.saturating_sub(received);
+
+    let vault = &ctx.accounts.vault;
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.escrow_ata.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, funded, ctx.accounts.usdc_mint.decimals)?;
+
+    // The fee reserve counts as sent until it is charged
+    let vault = &mut ctx.accounts.vault;
+    helpers::record_usdc_outflow(vault, funded, 0)?;
+    let escrow_id = vault.escrow_count;
+    vault.escrow_count = vault.escrow_count.checked_add(1).ok_or(VaultError::Overflow)?;
+    vault.open_escrows = vault.open_escrows.checked_add(1).ok_or(VaultError::Overflow)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.vault = vault.key();
+    escrow.escrow_id = escrow_id;
+    escrow.payer = payer_key;
+    escrow.payee = ctx.accounts.payee.key();
+    escrow.payee_ata = ctx.accounts.payee_ata.key();
+    escrow.escrow_ata = ctx.accounts.escrow_ata.key();
+    escrow.arbiter = arbiter;
+    escrow.amount = received;
+    escrow.fee_bps = fee_bps;
+    escrow.fee_reserve = fee_reserve;
+    escrow.deadline = deadline;
+    escrow.created_at = now;
+    escrow.bump = ctx.bumps.escrow;
+
+    emit!(EscrowCreated {
+        vault: vault.key(),
+        escrow_id,
+        payer: payer_key,
+        payee: escrow.payee,
+        arbiter,
+        amount: received,
+        fee_reserve,
+        deadline,
+        tier,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Moves `rate_per_second * (end_at - start_at)` USDC from the vault into a new
+/// stream that the recipient can draw from as it vests, together with the
+/// protocol fee on it. Agents are held to their tiers and budgets on the full
+/// stream amount.
+#[derive(Accounts)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// Role account when the creator is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), creator.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Recipient wallet address
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = recipient_ata.mint == vault.usdc_mint @ VaultError::InvalidTokenAccount,
+        constraint = recipient_ata.owner == recipient.key() @ VaultError::InvalidTokenAccount,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [
+            Stream::SEED_PREFIX,
+            vault.key().as_ref(),
+            vault.stream_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+
+    /// The stream's USDC account, owned by the stream PDA
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = stream,
+        associated_token::token_program = token_program,
+    )]
+    pub stream_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config; its fee rate is locked into the stream
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(
+    ctx: Context<CreateStream>,
+    rate_per_second: u64,
+    start_at: i64,
+    end_at: i64,
+    is_emergency: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(rate_per_second > 0, VaultError::ZeroAmount);
+    // Backdating would hand the recipient funds that vested before the stream existed
+    require!(start_at >= now, VaultError::InvalidSchedule);
+    require!(end_at > start_at, VaultError::InvalidSchedule);
+
+    let amount = end_at
+        .checked_sub(start_at)
+        .and_then(|duration| (duration as u64).checked_mul(rate_per_second))
+        .ok_or(VaultError::Overflow)?;
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    let vault = &ctx.accounts.vault;
+    let creator_key = ctx.accounts.creator.key();
+    let is_human = creator_key == vault.human;
+    let is_agent = creator_key == vault.agent || ctx.accounts.agent_role.is_some();
+
+    // Must be human or a registered agent
+    require!(is_human || is_agent, VaultError::OnlyAgentOrHuman);
+
+    let mut tier: u8 = 0;
+    if !is_human {
+        require!(!vault.paused, VaultError::VaultPaused);
+        let limits = helpers::agent_limits(vault, &creator_key, ctx.accounts.agent_role.as_deref())?;
+        tier = helpers::agent_tier(received, &limits, is_emergency)?;
+
+        let vault: &mut Vault = &mut ctx.accounts.vault;
+        helpers::record_budget_spend(&mut vault.daily_budget, &mut vault.weekly_budget, received, now)?;
+    }
+
+    // Fund the stream, fee included, using vault PDA as signer
+    let fee_bps = ctx.accounts.protocol_config.fee_bps;
+    let funded = helpers::protocol_fee(received, fee_bps)?
+        .checked_add(amount)
+        .ok_or(VaultError::Overflow)?;
+    let fee_reserve = helpers::amount_received(&ctx.accounts.usdc_mint, funded)?.saturating_sub(received);
+
+    let vault = &ctx.accounts.vault;
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.stream_ata.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, funded, ctx.accounts.usdc_mint.decimals)?;
+
+    // The fee reserve counts as sent until it is charged
+    let vault = &mut ctx.accounts.vault;
+    helpers::record_usdc_outflow(vault, funded, 0)?;
+    let stream_id = vault.stream_count;
+    vault.stream_count = vault.stream_count.checked_add(1).ok_or(VaultError::Overflow)?;
+    vault.open_streams = vault.open_streams.checked_add(1).ok_or(VaultError::Overflow)?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.vault = vault.key();
+    stream.stream_id = stream_id;
+    stream.creator = creator_key;
+    stream.recipient = ctx.accounts.recipient.key();
+    stream.recipient_ata = ctx.accounts.recipient_ata.key();
+    stream.stream_ata = ctx.accounts.stream_ata.key();
+    stream.rate_per_second = rate_per_second;
+    stream.start_at = start_at;
+    stream.end_at = end_at;
+    stream.deposited = received;
+    stream.withdrawn = 0;
+    stream.fee_bps = fee_bps;
+    stream.fee_reserve = fee_reserve;
+    stream.bump = ctx.bumps.stream;
+
+    emit!(StreamCreated {
+        vault: vault.key(),
+        stream_id,
+        creator: creator_key,
+        recipient: stream.recipient,
+        rate_per_second,
+        start_at,
+        end_at,
+        deposited: received,
+        fee_reserve,
+        tier,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CreateSubscription<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Recipient wallet address
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = recipient_ata.mint == vault.usdc_mint @ VaultError::InvalidTokenAccount,
+        constraint = recipient_ata.owner == recipient.key() @ VaultError::InvalidTokenAccount,
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = human,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [
+            Subscription::SEED_PREFIX,
+            vault.key().as_ref(),
+            vault.subscription_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateSubscription>,
+    amount: u64,
+    interval: i64,
+    start_at: i64,
+    end_at: Option<i64>,
+    max_payments: Option<u32>,
+) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+    require!(interval > 0, VaultError::InvalidSchedule);
+    let now = Clock::get()?.unix_timestamp;
+    require!(start_at >= now, VaultError::InvalidSchedule);
+    if let Some(end_at) = end_at {
+        require!(end_at >= start_at, VaultError::InvalidSchedule);
+    }
+    if let Some(max_payments) = max_payments {
+        require!(max_payments > 0, VaultError::InvalidSchedule);
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    let subscription_id = vault.subscription_count;
+    vault.subscription_count = vault
+        .subscription_count
+        .checked_add(1)
+        .ok_or(VaultError::Overflow)?;
+    vault.open_subscriptions = vault
+        .open_subscriptions
+        .checked_add(1)
+        .ok_or(VaultError::Overflow)?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.vault = vault.key();
+    subscription.subscription_id = subscription_id;
+    subscription.recipient = ctx.accounts.recipient.key();
+    subscription.recipient_ata = ctx.accounts.recipient_ata.key();
+    subscription.amount = amount;
+    subscription.interval = interval;
+    subscription.next_payment_at = start_at;
+    subscription.end_at = end_at;
+    subscription.max_payments = max_payments;
+    subscription.payments_made = 0;
+    subscription.paused = false;
+    subscription.created_at = now;
+    subscription.bump = ctx.bumps.subscription;
+
+    emit!(SubscriptionCreated {
+        vault: vault.key(),
+        subscription_id,
+        recipient: subscription.recipient,
+        amount,
+        interval,
+        start_at,
+        end_at,
+        max_payments,
+    });
+
+    Ok(())
+}
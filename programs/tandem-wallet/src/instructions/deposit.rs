@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Maximum length of a deposit reference.
+pub const MAX_REFERENCE_LEN: usize = 64;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Depositor's USDC token account
+    #[account(
+        mut,
+        constraint = depositor_ata.mint == vault.usdc_mint @ VaultError::InvalidTokenAccount,
+    )]
+    pub depositor_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<Deposit>, amount: u64, reference: Option<String>) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+    if let Some(ref reference) = reference {
+        require!(reference.len() <= MAX_REFERENCE_LEN, VaultError::ReferenceTooLong);
+    }
+
+    // Count what the vault actually receives
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.depositor_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.vault_usdc_ata.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_deposited = vault
+        .total_deposited
+        .checked_add(received)
+        .ok_or(VaultError::Overflow)?;
+
+    emit!(Deposited {
+        vault: vault.key(),
+        depositor: ctx.accounts.depositor.key(),
+        amount: received,
+        reference,
+        total_deposited: vault.total_deposited,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Pays out a proposal once it is executable: after the vault's execution delay
+/// for one scheduled by `approve_proposal`, or after the veto period for an
+/// optimistic one that nobody cancelled. Anyone can call it. An optimistic
+/// proposal must still fit the vault's band, and its proposer must still be an
+/// agent allowed to propose.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = !vault.paused @ VaultError::VaultPaused,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            Proposal::SEED_PREFIX,
+            vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.vault == vault.key(),
+        constraint = !proposal.executed @ VaultError::ProposalAlreadyExecuted,
+        constraint = !proposal.cancelled @ VaultError::ProposalAlreadyCancelled,
+        constraint = proposal.is_scheduled() @ VaultError::ProposalNotScheduled,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Role account when the proposer is an additional (non-primary) agent;
+    /// only read for optimistic proposals.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), proposal.proposer.as_ref()],
+        bump = proposer_role.bump,
+    )]
+    pub proposer_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        constraint = mint.key() == proposal.mint @ VaultError::InvalidTokenAccount,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Config for the proposal's mint; required unless it pays out in USDC.
+    #[account(
+        mut,
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), proposal.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
+    /// The vault's token account for the proposal's mint (checked in handler)
+    #[account(mut)]
+    pub vault_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Checked against the proposal; may be created in this instruction
+    #[account(
+        mut,
+        constraint = recipient_ata.key() == proposal.recipient_ata,
+    )]
+    pub recipient_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient wallet. Pass it to create `recipient_ata` as their associated
+    /// token account on execution if it doesn't exist yet; the executor pays the rent.
+    #[account(
+        constraint = recipient.key() == proposal.recipient,
+    )]
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback wallet's account for the proposal's mint; required unless it pays out in USDC
+    #[account(
+        mut,
+        constraint = token_buyback_ata.mint == proposal.mint @ VaultError::InvalidTokenAccount,
+        constraint = token_buyback_ata.owner == buyback_ata.owner @ VaultError::InvalidTokenAccount,
+    )]
+    pub token_buyback_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &ctx.accounts.proposal;
+    let executable_at = proposal.executable_at.ok_or(VaultError::ProposalNotScheduled)?;
+    require!(now >= executable_at, VaultError::ExecutionDelayNotElapsed);
+    require!(!proposal.is_expired(now), VaultError::ProposalExpired);
+
+    // Nobody approved an optimistic proposal, so the vault's current settings decide
+    if proposal.approved_at.is_none() {
+        let vault = &ctx.accounts.vault;
+        require!(
+            vault.optimistic_max > 0
+                && proposal.mint == vault.usdc_mint
+                && proposal.amount <= vault.optimistic_max,
+            VaultError::OutsideOptimisticBand
+        );
+        let limits = helpers::agent_limits(vault, &proposal.proposer, ctx.accounts.proposer_role.as_deref())?;
+        require!(limits.can_propose, VaultError::AgentCannotPropose);
+    }
+
+    let requested_amount = proposal.amount;
+    let amount = proposal.approved_amount.unwrap_or(requested_amount);
+
+    if let Some(ref recipient) = ctx.accounts.recipient {
+        helpers::create_ata_idempotent(
+            ctx.accounts.executor.to_account_info(),
+            ctx.accounts.recipient_ata.to_account_info(),
+            recipient.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.associated_token_program.to_account_info(),
+        )?;
+    }
+
+    let (received, fee) = helpers::pay_proposal(
+        helpers::ProposalPayout {
+            vault: &mut ctx.accounts.vault,
+            mint_config: ctx.accounts.mint_config.as_deref_mut(),
+            mint: &ctx.accounts.mint,
+            vault_token_ata: &ctx.accounts.vault_token_ata,
+            recipient_ata: ctx.accounts.recipient_ata.to_account_info(),
+            protocol_config: &ctx.accounts.protocol_config,
+            staker_reward_ata: &ctx.accounts.staker_reward_ata,
+            buyback_ata: &ctx.accounts.buyback_ata,
+            token_buyback_ata: ctx.accounts.token_buyback_ata.as_deref(),
+            token_program: &ctx.accounts.token_program,
+        },
+        &mut ctx.accounts.proposal,
+        amount,
+        now,
+    )?;
+
+    let proposal = &ctx.accounts.proposal;
+    emit!(ProposalApproved {
+        vault: ctx.accounts.vault.key(),
+        proposal_id: proposal.proposal_id,
+        sequence: proposal.sequence,
+        mint: proposal.mint,
+        recipient: proposal.recipient,
+        requested_amount,
+        approved_amount: amount,
+        amount: received,
+        fee,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    /// Anyone can execute a recovery once its timelock has passed.
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [RecoveryRequest::SEED_PREFIX, vault.key().as_ref()],
+        bump = recovery_request.bump,
+        constraint = recovery_request.vault == vault.key(),
+        close = initiator,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// CHECK: Receives the rent; must be the guardian that opened the request
+    #[account(
+        mut,
+        constraint = initiator.key() == recovery_request.initiator,
+    )]
+    pub initiator: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteRecovery>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let request = &ctx.accounts.recovery_request;
+    let vault = &mut ctx.accounts.vault;
+
+    // Votes are recounted against the current guardian set
+    let approvals = request
+        .approvals
+        .iter()
+        .filter(|g| vault.guardians.contains(g))
+        .count();
+    let ready = match request.executable_at {
+        Some(executable_at) => now >= executable_at,
+        None => false,
+    };
+    require!(
+        ready && vault.guardian_threshold > 0 && approvals >= vault.guardian_threshold as usize,
+        VaultError::RecoveryNotReady
+    );
+
+    let old_human = vault.human;
+    vault.human = request.new_human;
+    vault.pending_human = None;
+
+    emit!(RecoveryExecuted {
+        vault: vault.key(),
+        old_human,
+        new_human: vault.human,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::errors::*;
@@ -13,7 +13,7 @@ pub struct Initialize<'info> {
     /// CHECK: Agent pubkey, doesn't need to sign initialization
     pub agent: UncheckedAccount<'info>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
@@ -29,11 +29,12 @@ pub struct Initialize<'info> {
         payer = human,
         associated_token::mint = usdc_mint,
         associated_token::authority = vault,
+        associated_token::token_program = token_program,
     )]
-    pub vault_usdc_ata: Account<'info, TokenAccount>,
+    pub vault_usdc_ata: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -43,13 +44,44 @@ pub fn handler(ctx: Context<Initialize>, tier1_max: u64, tier2_max: u64) -> Resu
 
     let vault = &mut ctx.accounts.vault;
     vault.human = ctx.accounts.human.key();
+    vault.seed_human = ctx.accounts.human.key();
+    vault.pending_human = None;
     vault.agent = ctx.accounts.agent.key();
+    vault.seed_agent = ctx.accounts.agent.key();
     vault.usdc_mint = ctx.accounts.usdc_mint.key();
     vault.vault_usdc_ata = ctx.accounts.vault_usdc_ata.key();
     vault.tier1_max = tier1_max;
     vault.tier2_max = tier2_max;
     vault.paused = false;
     vault.proposal_count = 0;
+    vault.daily_budget = SpendWindow::new(0, SpendWindow::DAY_SECONDS);
+    vault.weekly_budget = SpendWindow::new(0, SpendWindow::WEEK_SECONDS);
+    vault.whitelist_delay = 0;
+    vault.pending_whitelist_delay = None;
+    vault.proposal_ttl = 0;
+    vault.execution_delay = 0;
+    vault.pending_execution_delay = None;
+    vault.optimistic_max = 0;
+    vault.veto_period = 0;
+    vault.approvers = Vec::new();
+    vault.approval_threshold = 0;
+    vault.guardians = Vec::new();
+    vault.guardian_threshold = 0;
+    vault.recovery_delay = 0;
+    vault.total_deposited = 0;
+    vault.total_sent = 0;
+    vault.total_fees_paid = 0;
+    vault.subscription_count = 0;
+    vault.stream_count = 0;
+    vault.escrow_count = 0;
+    vault.open_subscriptions = 0;
+    vault.open_streams = 0;
+    vault.open_escrows = 0;
+    vault.mint_configs = 0;
+    vault.agent_roles = 0;
+    vault.whitelist_entries = 0;
+    vault.open_proposals = 0;
+    vault.agent_ledgers = 0;
     vault.bump = ctx.bumps.vault;
 
     emit!(VaultInitialized {
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::events::*;
@@ -18,8 +18,8 @@ pub struct InitializeProtocol<'info> {
     )]
     pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
-    pub usdc_mint: Box<Account<'info, Mint>>,
-    pub tandem_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub tandem_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// USDC ATA owned by the protocol_config PDA — staker rewards accumulate here
     #[account(
@@ -27,14 +27,15 @@ pub struct InitializeProtocol<'info> {
         payer = authority,
         associated_token::mint = usdc_mint,
         associated_token::authority = protocol_config,
+        associated_token::token_program = token_program,
     )]
-    pub staker_reward_ata: Box<Account<'info, TokenAccount>>,
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// USDC ATA for buyback wallet
     #[account(
         constraint = buyback_ata.mint == usdc_mint.key(),
     )]
-    pub buyback_ata: Box<Account<'info, TokenAccount>>,
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// TANDEM ATA owned by the protocol_config PDA — staked tokens held here
     #[account(
@@ -42,11 +43,15 @@ pub struct InitializeProtocol<'info> {
         payer = authority,
         associated_token::mint = tandem_mint,
         associated_token::authority = protocol_config,
+        associated_token::token_program = tandem_token_program,
     )]
-    pub stake_tandem_ata: Box<Account<'info, TokenAccount>>,
+    pub stake_tandem_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    /// Token program owning the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program owning the TANDEM mint (may differ from USDC's)
+    pub tandem_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
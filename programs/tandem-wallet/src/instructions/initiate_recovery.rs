@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.guardians.contains(&guardian.key()) @ VaultError::OnlyGuardian,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = guardian,
+        space = 8 + RecoveryRequest::INIT_SPACE,
+        seeds = [RecoveryRequest::SEED_PREFIX, vault.key().as_ref()],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitiateRecovery>, new_human: Pubkey) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = &ctx.accounts.vault;
+    let guardian = ctx.accounts.guardian.key();
+
+    let request = &mut ctx.accounts.recovery_request;
+    request.vault = vault.key();
+    request.new_human = new_human;
+    request.initiator = guardian;
+    request.approvals = Vec::new();
+    request.initiated_at = now;
+    request.executable_at = None;
+    request.bump = ctx.bumps.recovery_request;
+    helpers::record_recovery_vote(vault, request, guardian, now)?;
+
+    emit!(RecoveryInitiated {
+        vault: vault.key(),
+        guardian,
+        new_human,
+    });
+    emit!(RecoveryApproved {
+        vault: vault.key(),
+        guardian,
+        approvals: request.approvals.len() as u8,
+        threshold: vault.guardian_threshold,
+        executable_at: request.executable_at,
+    });
+
+    Ok(())
+}
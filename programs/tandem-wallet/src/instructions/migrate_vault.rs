@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+/// Upgrades a vault created before agent rotation to the current layout.
+/// Legacy whitelist entries and proposals of the vault can be passed as
+/// remaining accounts and are migrated in the same instruction; any left out
+/// can be migrated later with `migrate_vault_accounts`.
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    /// CHECK: Legacy vault; layout and PDA derivation are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateVault<'info>>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let legacy: LegacyVault = read_legacy(&vault_info, &Vault::DISCRIMINATOR, LegacyVault::SPACE)?;
+    require_keys_eq!(legacy.human, ctx.accounts.human.key(), VaultError::OnlyHuman);
+
+    let expected = Pubkey::create_program_address(
+        &[
+            Vault::SEED_PREFIX,
+            legacy.human.as_ref(),
+            legacy.agent.as_ref(),
+            &[legacy.bump],
+        ],
+        ctx.program_id,
+    )
+    .map_err(|_| VaultError::NotLegacyAccount)?;
+    require_keys_eq!(expected, vault_info.key(), VaultError::NotLegacyAccount);
+
+    let mut vault = Vault {
+        human: legacy.human,
+        seed_human: legacy.human,
+        pending_human: None,
+        agent: legacy.agent,
+        seed_agent: legacy.agent,
+        usdc_mint: legacy.usdc_mint,
+        vault_usdc_ata: legacy.vault_usdc_ata,
+        tier1_max: legacy.tier1_max,
+        tier2_max: legacy.tier2_max,
+        paused: legacy.paused,
+        proposal_count: legacy.proposal_count,
+        daily_budget: SpendWindow::new(0, SpendWindow::DAY_SECONDS),
+        weekly_budget: SpendWindow::new(0, SpendWindow::WEEK_SECONDS),
+        whitelist_delay: 0,
+        pending_whitelist_delay: None,
+        proposal_ttl: 0,
+        execution_delay: 0,
+        pending_execution_delay: None,
+        optimistic_max: 0,
+        veto_period: 0,
+        approvers: Vec::new(),
+        approval_threshold: 0,
+        guardians: Vec::new(),
+        guardian_threshold: 0,
+        recovery_delay: 0,
+        total_deposited: 0,
+        total_sent: 0,
+        total_fees_paid: 0,
+        subscription_count: 0,
+        stream_count: 0,
+        escrow_count: 0,
+        open_subscriptions: 0,
+        open_streams: 0,
+        open_escrows: 0,
+        mint_configs: 0,
+        agent_roles: 0,
+        whitelist_entries: 0,
+        open_proposals: 0,
+        agent_ledgers: 0,
+        bump: legacy.bump,
+    };
+
+    let (whitelist_entries, proposals) = migrate_children(
+        ctx.remaining_accounts,
+        &vault,
+        vault_info.key(),
+        &ctx.accounts.human,
+        &ctx.accounts.system_program,
+    )?;
+
+    // Legacy proposals were all keyed by the vault's counter
+    vault.whitelist_entries = whitelist_entries;
+    vault.open_proposals = proposals;
+    write_migrated(
+        &vault_info,
+        &vault,
+        8 + Vault::INIT_SPACE,
+        &ctx.accounts.human,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(VaultMigrated {
+        vault: vault_info.key(),
+        whitelist_entries,
+        proposals,
+    });
+
+    Ok(())
+}
+
+/// Migrate legacy whitelist entries and proposals of `vault`, returning how
+/// many of each were migrated.
+pub(crate) fn migrate_children<'info>(
+    accounts: &[AccountInfo<'info>],
+    vault: &Vault,
+    vault_key: Pubkey,
+    human: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<(u32, u32)> {
+    let mut whitelist_entries: u32 = 0;
+    let mut proposals: u32 = 0;
+
+    for info in accounts.iter() {
+        require_keys_eq!(*info.owner, crate::ID, VaultError::NotLegacyAccount);
+        let discriminator: [u8; 8] = info
+            .try_borrow_data()?
+            .get(..8)
+            .and_then(|d| d.try_into().ok())
+            .ok_or(VaultError::NotLegacyAccount)?;
+
+        if discriminator == WhitelistEntry::DISCRIMINATOR {
+            let legacy: LegacyWhitelistEntry =
+                read_legacy(info, &WhitelistEntry::DISCRIMINATOR, LegacyWhitelistEntry::SPACE)?;
+            require_keys_eq!(legacy.vault, vault_key, VaultError::NotLegacyAccount);
+
+            // Entries that were already usable stay usable and unlimited
+            let entry = WhitelistEntry {
+                vault: legacy.vault,
+                address: legacy.address,
+                added_at: legacy.added_at,
+                active_from: legacy.added_at,
+                expires_at: None,
+                max_per_tx: 0,
+                period_budget: SpendWindow::default(),
+                bump: legacy.bump,
+            };
+            write_migrated(
+                info,
+                &entry,
+                8 + WhitelistEntry::INIT_SPACE,
+                human,
+                system_program,
+            )?;
+            whitelist_entries += 1;
+        } else if discriminator == Proposal::DISCRIMINATOR {
+            let legacy: LegacyProposal =
+                read_legacy(info, &Proposal::DISCRIMINATOR, LegacyProposal::SPACE)?;
+            require_keys_eq!(legacy.vault, vault_key, VaultError::NotLegacyAccount);
+
+            // Legacy proposals could only be created (and paid for) by the vault's agent
+            let proposal = Proposal {
+                vault: legacy.vault,
+                proposal_id: legacy.proposal_id,
+                sequence: legacy.proposal_id,
+                proposer: vault.seed_agent,
+                recipient: legacy.recipient,
+                mint: vault.usdc_mint,
+                recipient_ata: legacy.recipient_ata,
+                amount: legacy.amount,
+                approved_amount: if legacy.executed { Some(legacy.amount) } else { None },
+                proposed_at: legacy.proposed_at,
+                expires_at: None,
+                approved_at: None,
+                executable_at: None,
+                optimistic: false,
+                executed: legacy.executed,
+                cancelled: legacy.cancelled,
+                cancel_reason: if legacy.cancelled { Some(CancelReason::Other) } else { None },
+                cancel_note: String::new(),
+                approvals: Vec::new(),
+                approval_amounts: Vec::new(),
+                memo: legacy.memo,
+                revision: 0,
+                bump: legacy.bump,
+            };
+            write_migrated(
+                info,
+                &proposal,
+                8 + Proposal::INIT_SPACE,
+                human,
+                system_program,
+            )?;
+            proposals += 1;
+        } else {
+            return err!(VaultError::NotLegacyAccount);
+        }
+    }
+
+    Ok((whitelist_entries, proposals))
+}
+
+/// Deserialize a program account that still has its pre-migration size.
+fn read_legacy<T: AnchorDeserialize>(
+    info: &AccountInfo,
+    discriminator: &[u8],
+    space: usize,
+) -> Result<T> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() == 8 + space && &data[..8] == discriminator,
+        VaultError::NotLegacyAccount
+    );
+    T::deserialize(&mut &data[8..]).map_err(|_| error!(VaultError::NotLegacyAccount))
+}
+
+/// Grow a program account to `space`, topping up rent from `payer`, and write `value`.
+fn write_migrated<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    value: &T,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let shortfall = rent.saturating_sub(info.lamports());
+    if shortfall > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: info.clone(),
+            },
+        );
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+
+    info.realloc(space, false)?;
+    let mut data = info.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
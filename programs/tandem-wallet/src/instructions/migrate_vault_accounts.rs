@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::migrate_vault::migrate_children;
+
+/// Migrates legacy whitelist entries and proposals, passed as remaining
+/// accounts, of a vault that `migrate_vault` has already upgraded.
+#[derive(Accounts)]
+pub struct MigrateVaultAccounts<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateVaultAccounts<'info>>) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let (whitelist_entries, proposals) = migrate_children(
+        ctx.remaining_accounts,
+        &ctx.accounts.vault,
+        vault_key,
+        &ctx.accounts.human,
+        &ctx.accounts.system_program,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.whitelist_entries = vault
+        .whitelist_entries
+        .checked_add(whitelist_entries)
+        .ok_or(VaultError::Overflow)?;
+    vault.open_proposals = vault
+        .open_proposals
+        .checked_add(proposals)
+        .ok_or(VaultError::Overflow)?;
+
+    emit!(VaultAccountsMigrated {
+        vault: vault_key,
+        whitelist_entries,
+        proposals,
+    });
+
+    Ok(())
+}
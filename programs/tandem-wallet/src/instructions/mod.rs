@@ -1,12 +1,63 @@
 pub mod initialize;
+pub mod deposit;
+pub mod reconcile;
+pub mod withdraw;
+pub mod close_vault;
 pub mod send_usdc;
+pub mod send_usdc_batch;
+pub mod send_token;
 pub mod propose;
+pub mod propose_with_nonce;
+pub mod open_agent_ledger;
 pub mod approve_proposal;
+pub mod execute_proposal;
+pub mod approve_proposals;
+pub mod cancel_proposals;
 pub mod cancel_proposal;
+pub mod amend_proposal;
+pub mod withdraw_proposal;
 pub mod close_proposal;
+pub mod close_expired_proposal;
+pub mod set_proposal_ttl;
+pub mod set_execution_delay;
+pub mod set_optimistic_band;
 pub mod set_tiers;
+pub mod set_budgets;
+pub mod add_mint;
+pub mod set_mint_limits;
+pub mod remove_mint;
 pub mod add_whitelist;
+pub mod update_whitelist;
 pub mod remove_whitelist;
+pub mod cancel_whitelist;
+pub mod set_whitelist_delay;
+pub mod add_agent;
+pub mod update_agent;
+pub mod remove_agent;
+pub mod rotate_agent;
+pub mod migrate_vault;
+pub mod migrate_vault_accounts;
+pub mod propose_human_transfer;
+pub mod cancel_human_transfer;
+pub mod accept_human_transfer;
+pub mod set_guardians;
+pub mod initiate_recovery;
+pub mod approve_recovery;
+pub mod veto_recovery;
+pub mod execute_recovery;
+pub mod add_approver;
+pub mod remove_approver;
+pub mod set_approval_threshold;
+pub mod create_subscription;
+pub mod pay_subscription;
+pub mod set_subscription_paused;
+pub mod cancel_subscription;
+pub mod create_stream;
+pub mod withdraw_stream;
+pub mod cancel_stream;
+pub mod create_escrow;
+pub mod release_escrow;
+pub mod refund_escrow;
 pub mod pause;
 pub mod unpause;
 pub mod initialize_protocol;
@@ -16,14 +67,65 @@ pub mod claim_rewards;
 pub mod update_protocol_config;
 
 pub use initialize::*;
+pub use deposit::*;
+pub use reconcile::*;
+pub use withdraw::*;
+pub use close_vault::*;
 pub use send_usdc::*;
+pub use send_usdc_batch::*;
+pub use send_token::*;
 pub use propose::*;
+pub use propose_with_nonce::*;
+pub use open_agent_ledger::*;
 pub use approve_proposal::*;
+pub use execute_proposal::*;
+pub use approve_proposals::*;
+pub use cancel_proposals::*;
 pub use cancel_proposal::*;
+pub use amend_proposal::*;
+pub use withdraw_proposal::*;
 pub use close_proposal::*;
+pub use close_expired_proposal::*;
+pub use set_proposal_ttl::*;
+pub use set_execution_delay::*;
+pub use set_optimistic_band::*;
 pub use set_tiers::*;
+pub use set_budgets::*;
+pub use add_mint::*;
+pub use set_mint_limits::*;
+pub use remove_mint::*;
 pub use add_whitelist::*;
+pub use update_whitelist::*;
 pub use remove_whitelist::*;
+pub use cancel_whitelist::*;
+pub use set_whitelist_delay::*;
+pub use add_agent::*;
+pub use update_agent::*;
+pub use remove_agent::*;
+pub use rotate_agent::*;
+pub use migrate_vault::*;
+pub use migrate_vault_accounts::*;
+pub use propose_human_transfer::*;
+pub use cancel_human_transfer::*;
+pub use accept_human_transfer::*;
+pub use set_guardians::*;
+pub use initiate_recovery::*;
+pub use approve_recovery::*;
+pub use veto_recovery::*;
+pub use execute_recovery::*;
+pub use add_approver::*;
+pub use remove_approver::*;
+pub use set_approval_threshold::*;
+pub use create_subscription::*;
+pub use pay_subscription::*;
+pub use set_subscription_paused::*;
+pub use cancel_subscription::*;
+pub use create_stream::*;
+pub use withdraw_stream::*;
+pub use cancel_stream::*;
+pub use create_escrow::*;
+pub use release_escrow::*;
+pub use refund_escrow::*;
 pub use pause::*;
 pub use unpause::*;
 pub use initialize_protocol::*;
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Creates the ledger `propose_with_nonce` counts an agent's proposals on.
+/// Each agent opens its own, once per vault, before its first nonce proposal.
+#[derive(Accounts)]
+pub struct OpenAgentLedger<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + AgentLedger::INIT_SPACE,
+        seeds = [AgentLedger::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump,
+    )]
+    pub agent_ledger: Account<'info, AgentLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<OpenAgentLedger>) -> Result<()> {
+    let agent_key = ctx.accounts.agent.key();
+    helpers::agent_limits(&ctx.accounts.vault, &agent_key, ctx.accounts.agent_role.as_deref())?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.agent_ledgers = vault.agent_ledgers.checked_add(1).ok_or(VaultError::Overflow)?;
+
+    let ledger = &mut ctx.accounts.agent_ledger;
+    ledger.vault = vault.key();
+    ledger.agent = agent_key;
+    ledger.open_proposals = 0;
+    ledger.proposal_count = 0;
+    ledger.bump = ctx.bumps.agent_ledger;
+
+    emit!(AgentLedgerOpened {
+        vault: vault.key(),
+        agent: agent_key,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Runs one due subscription payment. Anyone can crank it; the schedule is
+/// enforced here and missed intervals are skipped. Payments were authorized by the human, so they don't count
+/// against the agent budgets.
+#[derive(Accounts)]
+pub struct PaySubscription<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = !vault.paused @ VaultError::VaultPaused,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            Subscription::SEED_PREFIX,
+            vault.key().as_ref(),
+            subscription.subscription_id.to_le_bytes().as_ref(),
+        ],
+        bump = subscription.bump,
+        constraint = !subscription.paused @ VaultError::SubscriptionPaused,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_ata.key() == subscription.recipient_ata,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<PaySubscription>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let subscription = &mut ctx.accounts.subscription;
+    require!(!subscription.is_ended(), VaultError::SubscriptionEnded);
+    require!(now >= subscription.next_payment_at, VaultError::SubscriptionNotDue);
+
+    // One payment per crank. Intervals nobody cranked in time (e.g. while the
+    // vault was paused) are skipped, not paid back-to-back
+    subscription.payments_made = subscription
+        .payments_made
+        .checked_add(1)
+        .ok_or(VaultError::Overflow)?;
+    subscription.next_payment_at = now
+        .checked_add(1)
+        .and_then(|after_now| subscription.slot_at_or_after(after_now))
+        .ok_or(VaultError::Overflow)?;
+
+    let amount = subscription.amount;
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    // Execute transfer using vault PDA as signer
+    let vault = &ctx.accounts.vault;
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.recipient_ata.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+
+    // Calculate and transfer fee
+    let fee = helpers::calculate_and_transfer_fee(
+        received,
+        ctx.accounts.protocol_config.fee_bps,
+        ctx.accounts.protocol_config.total_staked,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.vault_usdc_ata,
+        Some(&ctx.accounts.staker_reward_ata),
+        &ctx.accounts.buyback_ata,
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.token_program,
+        signer_seeds,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    helpers::record_usdc_outflow(vault, amount, fee)?;
+
+    let subscription = &ctx.accounts.subscription;
+    emit!(SubscriptionPaid {
+        vault: vault.key(),
+        subscription_id: subscription.subscription_id,
+        cranker: ctx.accounts.cranker.key(),
+        recipient: subscription.recipient,
+        amount: received,
+        fee,
+        payments_made: subscription.payments_made,
+        next_payment_at: subscription.next_payment_at,
+    });
+
+    Ok(())
+}
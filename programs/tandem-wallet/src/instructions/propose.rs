@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::helpers;
 
 #[derive(Accounts)]
 pub struct Propose<'info> {
@@ -10,17 +11,31 @@ pub struct Propose<'info> {
 
     #[account(
         mut,
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
-        constraint = vault.agent == agent.key() @ VaultError::OnlyAgent,
         constraint = !vault.paused @ VaultError::VaultPaused,
     )]
     pub vault: Account<'info, Vault>,
 
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    /// Config for the mint to pay out in; omit for the vault's USDC mint.
+    #[account(
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
     /// CHECK: Recipient wallet address
     pub recipient: UncheckedAccount<'info>,
 
-    /// CHECK: Recipient's USDC ATA - validated by client
+    /// CHECK: Recipient's token account for the proposal's mint, or the address of
+    /// their associated token account if it doesn't exist yet (checked in handler)
     pub recipient_ata: UncheckedAccount<'info>,
 
     #[account(
@@ -40,31 +55,113 @@ pub struct Propose<'info> {
 }
 
 pub fn handler(ctx: Context<Propose>, amount: u64, memo: String) -> Result<()> {
-    require!(amount > 0, VaultError::ZeroAmount);
-    require!(memo.len() <= 128, VaultError::Overflow);
-
     let vault = &mut ctx.accounts.vault;
     let proposal_id = vault.proposal_count;
     vault.proposal_count = vault.proposal_count.checked_add(1).ok_or(VaultError::Overflow)?;
+    vault.open_proposals = vault.open_proposals.checked_add(1).ok_or(VaultError::Overflow)?;
+
+    create_proposal(
+        NewProposal {
+            agent: &ctx.accounts.agent,
+            vault: &ctx.accounts.vault,
+            agent_role: ctx.accounts.agent_role.as_deref(),
+            mint_config: ctx.accounts.mint_config.as_deref(),
+            recipient: &ctx.accounts.recipient,
+            recipient_ata: &ctx.accounts.recipient_ata,
+            proposal: &mut ctx.accounts.proposal,
+        },
+        proposal_id,
+        proposal_id,
+        ctx.bumps.proposal,
+        amount,
+        memo,
+    )
+}
+
+/// Accounts a new proposal is created from, shared by `propose` and `propose_with_nonce`.
+pub(crate) struct NewProposal<'a, 'info> {
+    pub agent: &'a Signer<'info>,
+    pub vault: &'a Account<'info, Vault>,
+    pub agent_role: Option<&'a AgentRole>,
+    pub mint_config: Option<&'a MintConfig>,
+    pub recipient: &'a UncheckedAccount<'info>,
+    pub recipient_ata: &'a UncheckedAccount<'info>,
+    pub proposal: &'a mut Account<'info, Proposal>,
+}
+
+/// Check the agent may propose `amount`, then write the proposal under `proposal_id`.
+pub(crate) fn create_proposal(
+    accounts: NewProposal,
+    proposal_id: u64,
+    sequence: u64,
+    bump: u8,
+    amount: u64,
+    memo: String,
+) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+    require!(memo.len() <= MAX_MEMO_LEN, VaultError::MemoTooLong);
+
+    let agent_key = accounts.agent.key();
+    let vault = accounts.vault;
+    let limits = helpers::agent_limits(vault, &agent_key, accounts.agent_role)?;
+    require!(limits.can_propose, VaultError::AgentCannotPropose);
+
+    let now = Clock::get()?.unix_timestamp;
+    let expires_at = if vault.proposal_ttl > 0 {
+        Some(now.checked_add(vault.proposal_ttl).ok_or(VaultError::Overflow)?)
+    } else {
+        None
+    };
+    let mint = match accounts.mint_config {
+        Some(mint_config) => mint_config.mint,
+        None => vault.usdc_mint,
+    };
+    helpers::check_recipient_token_account(
+        accounts.recipient_ata,
+        &accounts.recipient.key(),
+        &mint,
+    )?;
+    let executable_at = helpers::optimistic_executable_at(vault, &limits, &mint, amount, now)?;
 
-    let proposal = &mut ctx.accounts.proposal;
+    let proposal = accounts.proposal;
     proposal.vault = vault.key();
     proposal.proposal_id = proposal_id;
-    proposal.recipient = ctx.accounts.recipient.key();
-    proposal.recipient_ata = ctx.accounts.recipient_ata.key();
+    proposal.sequence = sequence;
+    proposal.proposer = agent_key;
+    proposal.recipient = accounts.recipient.key();
+    proposal.mint = mint;
+    proposal.recipient_ata = accounts.recipient_ata.key();
     proposal.amount = amount;
-    proposal.proposed_at = Clock::get()?.unix_timestamp;
+    proposal.approved_amount = None;
+    proposal.proposed_at = now;
+    proposal.expires_at = expires_at;
+    proposal.approved_at = None;
+    proposal.executable_at = executable_at;
+    proposal.optimistic = executable_at.is_some();
     proposal.executed = false;
     proposal.cancelled = false;
+    proposal.cancel_reason = None;
+    proposal.cancel_note = String::new();
+    proposal.approvals = Vec::new();
+    proposal.approval_amounts = Vec::new();
     proposal.memo = memo.clone();
-    proposal.bump = ctx.bumps.proposal;
+    proposal.revision = 0;
+    proposal.bump = bump;
 
     emit!(ProposalCreated {
         vault: vault.key(),
         proposal_id,
+        sequence,
+        agent: agent_key,
+        mint,
         recipient: proposal.recipient,
         amount,
         memo,
+        expires_at,
+        optimistic_max: vault.optimistic_max,
+        veto_period: vault.veto_period,
+        optimistic: proposal.optimistic,
+        executable_at,
     });
 
     Ok(())
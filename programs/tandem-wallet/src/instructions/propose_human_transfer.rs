@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ProposeHumanTransfer<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<ProposeHumanTransfer>, new_human: Pubkey) -> Result<()> {
+    // Ownership only moves once the new key signs accept_human_transfer
+    let vault = &mut ctx.accounts.vault;
+    vault.pending_human = Some(new_human);
+
+    emit!(HumanTransferProposed {
+        vault: vault.key(),
+        human: vault.human,
+        pending_human: new_human,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::propose::{create_proposal, NewProposal};
+
+/// Same as `propose`, but keyed by a nonce the agent picks instead of the
+/// vault's counter. The vault is only read, so agents can submit several
+/// proposals at once without contending on it; the proposal is counted on the
+/// agent's own `AgentLedger` instead. The nonce must have `Proposal::NONCE_FLAG`
+/// set so it never collides with a sequential ID.
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ProposeWithNonce<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = !vault.paused @ VaultError::VaultPaused,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        mut,
+        seeds = [AgentLedger::SEED_PREFIX, vault.key().as_ref(), agent.key().as_ref()],
+        bump = agent_ledger.bump,
+    )]
+    pub agent_ledger: Account<'info, AgentLedger>,
+
+    /// Config for the mint to pay out in; omit for the vault's USDC mint.
+    #[account(
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
+    /// CHECK: Recipient wallet address
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient's token account for the proposal's mint, or the address of
+    /// their associated token account if it doesn't exist yet (checked in handler)
+    pub recipient_ata: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [
+            Proposal::SEED_PREFIX,
+            vault.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeWithNonce>, nonce: u64, amount: u64, memo: String) -> Result<()> {
+    require!(nonce & Proposal::NONCE_FLAG != 0, VaultError::InvalidProposalNonce);
+
+    let ledger = &mut ctx.accounts.agent_ledger;
+    ledger.open_proposals = ledger.open_proposals.checked_add(1).ok_or(VaultError::Overflow)?;
+    ledger.proposal_count = ledger.proposal_count.checked_add(1).ok_or(VaultError::Overflow)?;
+    let sequence = ledger.proposal_count;
+
+    create_proposal(
+        NewProposal {
+            agent: &ctx.accounts.agent,
+            vault: &ctx.accounts.vault,
+            agent_role: ctx.accounts.agent_role.as_deref(),
+            mint_config: ctx.accounts.mint_config.as_deref(),
+            recipient: &ctx.accounts.recipient,
+            recipient_ata: &ctx.accounts.recipient_ata,
+            proposal: &mut ctx.accounts.proposal,
+        },
+        nonce,
+        sequence,
+        ctx.bumps.proposal,
+        amount,
+        memo,
+    )
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<Reconcile>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let actual_balance = ctx.accounts.vault_usdc_ata.amount;
+
+    // u64 counters can't overflow i128
+    let expected_balance = vault.total_deposited as i128
+        - vault.total_sent as i128
+        - vault.total_fees_paid as i128;
+
+    emit!(VaultReconciled {
+        vault: vault.key(),
+        total_deposited: vault.total_deposited,
+        total_sent: vault.total_sent,
+        total_fees_paid: vault.total_fees_paid,
+        expected_balance,
+        actual_balance,
+        difference: actual_balance as i128 - expected_balance,
+    });
+
+    Ok(())
+}
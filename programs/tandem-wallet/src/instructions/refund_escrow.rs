@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Returns the locked funds and the unused fee reserve to the vault and closes
+/// the escrow. The payer can refund once the deadline has passed, as long as it
+/// is still the human or an agent; the arbiter can refund at any time.
+#[derive(Accounts)]
+pub struct RefundEscrow<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: Receives the escrow's rent; must match escrow.payer
+    #[account(
+        mut,
+        constraint = payer.key() == escrow.payer,
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [Escrow::SEED_PREFIX, vault.key().as_ref(), escrow.escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = signer.key() == escrow.payer
+            || escrow.is_arbiter(&signer.key()) @ VaultError::NotEscrowParty,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), signer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == escrow.escrow_ata,
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<RefundEscrow>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &ctx.accounts.escrow;
+    let signer_key = ctx.accounts.signer.key();
+    require!(
+        escrow.is_arbiter(&signer_key) || now >= escrow.deadline,
+        VaultError::EscrowNotExpired
+    );
+
+    // The stored payer key may have been removed as an agent since
+    if !escrow.is_arbiter(&signer_key) && signer_key != ctx.accounts.vault.human {
+        helpers::agent_limits(&ctx.accounts.vault, &signer_key, ctx.accounts.agent_role.as_deref())?;
+    }
+
+    let amount = ctx.accounts.escrow_ata.amount;
+    let vault_key = ctx.accounts.vault.key();
+    let escrow_id = escrow.escrow_id.to_le_bytes();
+    let escrow_bump = escrow.bump;
+    let escrow_seeds = &[
+        Escrow::SEED_PREFIX,
+        vault_key.as_ref(),
+        escrow_id.as_ref(),
+        &[escrow_bump],
+    ];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.vault_usdc_ata.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        escrow_signer,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+    let returned = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_ata.to_account_info(),
+            destination: ctx.accounts.payer.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        escrow_signer,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    // What came back is no longer counted as sent
+    let vault: &mut Vault = &mut ctx.accounts.vault;
+    vault.total_sent = vault
+        .total_sent
+        .checked_sub(returned)
+        .ok_or(VaultError::Overflow)?;
+    vault.open_escrows = vault.open_escrows.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+    emit!(EscrowRefunded {
+        vault: vault_key,
+        escrow_id: ctx.accounts.escrow.escrow_id,
+        refunded_by: signer_key,
+        amount: returned,
+    });
+
+    Ok(())
+}
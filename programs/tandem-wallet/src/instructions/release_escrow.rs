@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Pays the locked funds to the payee and closes the escrow. Callable by the
+/// payer while still an agent, the human or the arbiter; the protocol fee comes
+/// out of the reserve locked in the escrow.
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: Receives the escrow's rent; must match escrow.payer
+    #[account(
+        mut,
+        constraint = payer.key() == escrow.payer,
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [Escrow::SEED_PREFIX, vault.key().as_ref(), escrow.escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = signer.key() == escrow.payer
+            || signer.key() == vault.human
+            || escrow.is_arbiter(&signer.key()) @ VaultError::NotEscrowParty,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), signer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == escrow.escrow_ata,
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = payee_ata.key() == escrow.payee_ata,
+    )]
+    pub payee_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<ReleaseEscrow>) -> Result<()> {
+    // The stored payer key may have been removed as an agent since
+    let signer_key = ctx.accounts.signer.key();
+    let escrow = &ctx.accounts.escrow;
+    if signer_key == escrow.payer
+        && signer_key != ctx.accounts.vault.human
+        && !escrow.is_arbiter(&signer_key)
+    {
+        helpers::agent_limits(&ctx.accounts.vault, &signer_key, ctx.accounts.agent_role.as_deref())?;
+    }
+
+    let vault_key = ctx.accounts.vault.key();
+    let escrow_id = ctx.accounts.escrow.escrow_id.to_le_bytes();
+    let escrow_bump = ctx.accounts.escrow.bump;
+    let escrow_seeds = &[
+        Escrow::SEED_PREFIX,
+        vault_key.as_ref(),
+        escrow_id.as_ref(),
+        &[escrow_bump],
+    ];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    // Fee comes out of the escrow's reserve, at the rate locked in at creation
+    let fee = helpers::calculate_and_transfer_fee(
+        ctx.accounts.escrow.amount,
+        ctx.accounts.escrow.fee_bps,
+        ctx.accounts.protocol_config.total_staked,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.escrow_ata,
+        Some(&ctx.accounts.staker_reward_ata),
+        &ctx.accounts.buyback_ata,
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.token_program,
+        escrow_signer,
+    )?;
+
+    // Everything left goes to the payee
+    ctx.accounts.escrow_ata.reload()?;
+    let amount = ctx.accounts.escrow_ata.amount;
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.payee_ata.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        escrow_signer,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_ata.to_account_info(),
+            destination: ctx.accounts.payer.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        },
+        escrow_signer,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    // The reserve was counted as sent; what's charged now counts as fees
+    let vault: &mut Vault = &mut ctx.accounts.vault;
+    vault.total_sent = vault.total_sent.checked_sub(fee).ok_or(VaultError::Overflow)?;
+    helpers::record_usdc_outflow(vault, 0, fee)?;
+    vault.open_escrows = vault.open_escrows.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+    emit!(EscrowReleased {
+        vault: vault_key,
+        escrow_id: ctx.accounts.escrow.escrow_id,
+        released_by: signer_key,
+        payee: ctx.accounts.escrow.payee,
+        amount: received,
+        fee,
+    });
+
+    Ok(())
+}
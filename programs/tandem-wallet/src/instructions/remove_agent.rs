@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RemoveAgent<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent_role.agent.as_ref()],
+        bump = agent_role.bump,
+        constraint = agent_role.vault == vault.key(),
+        close = human,
+    )]
+    pub agent_role: Account<'info, AgentRole>,
+}
+
+pub fn handler(ctx: Context<RemoveAgent>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.agent_roles = vault.agent_roles.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+    emit!(AgentRemoved {
+        vault: ctx.accounts.vault.key(),
+        agent: ctx.accounts.agent_role.agent,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RemoveApprover<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<RemoveApprover>, approver: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let index = vault
+        .approvers
+        .iter()
+        .position(|a| *a == approver)
+        .ok_or(VaultError::NotApprover)?;
+    vault.approvers.remove(index);
+
+    // Removing the last approver hands approval back to the human alone.
+    // Otherwise the threshold has to be lowered first so it stays reachable.
+    if vault.approvers.is_empty() {
+        vault.approval_threshold = 0;
+    } else {
+        require!(
+            vault.approval_threshold as usize <= vault.approvers.len(),
+            VaultError::InvalidApprovalThreshold
+        );
+    }
+
+    emit!(ApproverRemoved {
+        vault: vault.key(),
+        approver,
+        approval_threshold: vault.approval_threshold,
+    });
+
+    Ok(())
+}
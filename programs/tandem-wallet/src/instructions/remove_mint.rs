@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RemoveMint<'info> {
+    #[account(mut)]
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = human,
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// The vault's token account for this mint; must be emptied first
+    #[account(
+        mut,
+        constraint = vault_token_ata.key() == mint_config.vault_ata @ VaultError::InvalidTokenAccount,
+        constraint = vault_token_ata.amount == 0 @ VaultError::MintBalanceNotEmpty,
+    )]
+    pub vault_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<RemoveMint>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Close the vault's token account, returning its rent to the human
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_token_ata.to_account_info(),
+            destination: ctx.accounts.human.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.mint_configs = vault.mint_configs.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+    emit!(MintRemoved {
+        vault: vault.key(),
+        mint: ctx.accounts.mint_config.mint,
+    });
+
+    Ok(())
+}
@@ -9,7 +9,8 @@ pub struct RemoveWhitelist<'info> {
     pub human: Signer<'info>,
 
     #[account(
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
         constraint = vault.human == human.key() @ VaultError::OnlyHuman,
     )]
@@ -25,10 +26,13 @@ pub struct RemoveWhitelist<'info> {
     pub whitelist_entry: Account<'info, WhitelistEntry>,
 }
 
-pub fn handler(_ctx: Context<RemoveWhitelist>) -> Result<()> {
+pub fn handler(ctx: Context<RemoveWhitelist>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.whitelist_entries = vault.whitelist_entries.checked_sub(1).ok_or(VaultError::Overflow)?;
+
     emit!(WhitelistRemoved {
-        vault: _ctx.accounts.vault.key(),
-        address: _ctx.accounts.whitelist_entry.address,
+        vault: ctx.accounts.vault.key(),
+        address: ctx.accounts.whitelist_entry.address,
     });
 
     Ok(())
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(new_agent: Pubkey)]
+pub struct RotateAgent<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+        constraint = vault.agent != new_agent @ VaultError::AlreadyPrimaryAgent,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<RotateAgent>, new_agent: Pubkey) -> Result<()> {
+    // The vault address stays derived from `seed_agent`, so the ATA, whitelist
+    // entries and proposals all carry over to the new key.
+    let vault = &mut ctx.accounts.vault;
+    let old_agent = vault.agent;
+    vault.agent = new_agent;
+
+    emit!(AgentRotated {
+        vault: vault.key(),
+        old_agent,
+        new_agent,
+    });
+
+    Ok(())
+}
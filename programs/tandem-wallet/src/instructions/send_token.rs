@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+#[derive(Accounts)]
+pub struct SendToken<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        constraint = vault_token_ata.key() == mint_config.vault_ata @ VaultError::InvalidTokenAccount,
+    )]
+    pub vault_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_ata.mint == mint.key() @ VaultError::InvalidTokenAccount,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), signer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Buyback USDC ATA, identifies the buyback wallet
+    #[account(
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback wallet's account for this mint (receives 100% of fee)
+    #[account(
+        mut,
+        constraint = token_buyback_ata.mint == mint.key() @ VaultError::InvalidTokenAccount,
+        constraint = token_buyback_ata.owner == buyback_ata.owner @ VaultError::InvalidTokenAccount,
+    )]
+    pub token_buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handler(
+    ctx: Context<SendToken>,
+    amount: u64,
+    is_emergency: bool,
+    memo: String,
+    invoice_id: Option<String>,
+) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+    helpers::check_payment_memo(&memo, invoice_id.as_deref())?;
+
+    // Limits and events work on what the recipient actually gets
+    let received = helpers::amount_received(&ctx.accounts.mint, amount)?;
+
+    let vault = &ctx.accounts.vault;
+    let signer_key = ctx.accounts.signer.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    let is_human = signer_key == vault.human;
+    let is_agent = signer_key == vault.agent || ctx.accounts.agent_role.is_some();
+
+    // Must be human or a registered agent
+    require!(is_human || is_agent, VaultError::OnlyAgentOrHuman);
+
+    let mut tier: u8 = 0;
+
+    if !is_human {
+        // Agent flow: tiers and budgets come from the mint's config.
+        // Whitelist limits are USDC-denominated and don't apply here.
+        require!(!vault.paused, VaultError::VaultPaused);
+        helpers::agent_limits(vault, &signer_key, ctx.accounts.agent_role.as_deref())?;
+
+        let mint_config: &mut MintConfig = &mut ctx.accounts.mint_config;
+        if received <= mint_config.tier1_max {
+            tier = 1;
+        } else if received <= mint_config.tier2_max {
+            require!(is_emergency, VaultError::NotEmergency);
+            tier = 2;
+        } else {
+            return err!(VaultError::TierTooHigh);
+        }
+
+        helpers::record_budget_spend(
+            &mut mint_config.daily_budget,
+            &mut mint_config.weekly_budget,
+            received,
+            now,
+        )?;
+    }
+
+    // Execute transfer using vault PDA as signer
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault_token_ata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.recipient_ata.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    // Staking rewards are paid in USDC, so the whole fee goes to buyback
+    let fee = helpers::calculate_and_transfer_fee(
+        received,
+        ctx.accounts.protocol_config.fee_bps,
+        ctx.accounts.protocol_config.total_staked,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_ata,
+        None,
+        &ctx.accounts.token_buyback_ata,
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.token_program,
+        signer_seeds,
+    )?;
+
+    helpers::write_payment_memo(&ctx.accounts.memo_program, &memo, invoice_id.as_deref())?;
+
+    let mint_config = &ctx.accounts.mint_config;
+    emit!(TokenSent {
+        vault: vault.key(),
+        signer: signer_key,
+        mint: mint_config.mint,
+        recipient: ctx.accounts.recipient_ata.owner,
+        amount: received,
+        fee,
+        tier,
+        daily_remaining: mint_config.daily_budget.remaining(now),
+        weekly_remaining: mint_config.weekly_budget.remaining(now),
+        memo,
+        invoice_id,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
@@ -7,61 +9,111 @@ use crate::helpers;
 
 #[derive(Accounts)]
 pub struct SendUsdc<'info> {
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account(
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
         mut,
         constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
     )]
-    pub vault_usdc_ata: Account<'info, TokenAccount>,
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: Recipient's USDC token account; may be created in this instruction (checked in handler)
     #[account(mut)]
-    pub recipient_ata: Account<'info, TokenAccount>,
+    pub recipient_ata: UncheckedAccount<'info>,
 
-    /// Optional whitelist entry PDA. If provided and valid, bypasses tier checks.
+    /// CHECK: Recipient wallet. Pass it to create `recipient_ata` as their associated
+    /// token account first if it doesn't exist yet; the signer pays the rent.
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    /// Optional whitelist entry PDA. If provided, valid and active, its own limits replace tier checks.
     /// CHECK: Validated manually if present
+    #[account(mut)]
     pub whitelist_entry: Option<Account<'info, WhitelistEntry>>,
 
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), signer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
     /// Protocol config for fee calculation
     #[account(
         seeds = [ProtocolConfig::SEED_PREFIX],
         bump = protocol_config.bump,
     )]
-    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
     /// Staker reward USDC ATA (receives 50% of fee)
     #[account(
         mut,
         constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
     )]
-    pub staker_reward_ata: Account<'info, TokenAccount>,
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Buyback USDC ATA (receives 50% of fee)
     #[account(
         mut,
         constraint = buyback_ata.key() == protocol_config.buyback_ata,
     )]
-    pub buyback_ata: Account<'info, TokenAccount>,
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-pub fn handler(ctx: Context<SendUsdc>, amount: u64, is_emergency: bool) -> Result<()> {
+pub fn handler(
+    ctx: Context<SendUsdc>,
+    amount: u64,
+    is_emergency: bool,
+    memo: String,
+    invoice_id: Option<String>,
+) -> Result<()> {
     require!(amount > 0, VaultError::ZeroAmount);
+    helpers::check_payment_memo(&memo, invoice_id.as_deref())?;
+
+    if let Some(ref recipient) = ctx.accounts.recipient {
+        helpers::create_ata_idempotent(
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.recipient_ata.to_account_info(),
+            recipient.to_account_info(),
+            ctx.accounts.usdc_mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.associated_token_program.to_account_info(),
+        )?;
+    }
+    let recipient_ata =
+        helpers::read_token_account(&ctx.accounts.recipient_ata, &ctx.accounts.token_program.key())?;
+    require_keys_eq!(recipient_ata.mint, ctx.accounts.usdc_mint.key(), VaultError::InvalidTokenAccount);
+    let recipient_owner = recipient_ata.owner;
+
+    // Limits and events work on what the recipient actually gets
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
 
     let vault = &ctx.accounts.vault;
     let signer_key = ctx.accounts.signer.key();
+    let now = Clock::get()?.unix_timestamp;
 
     let is_human = signer_key == vault.human;
-    let is_agent = signer_key == vault.agent;
+    let is_agent = signer_key == vault.agent || ctx.accounts.agent_role.is_some();
 
-    // Must be human or agent
+    // Must be human or a registered agent
     require!(is_human || is_agent, VaultError::OnlyAgentOrHuman);
 
     let mut tier: u8 = 0;
@@ -73,21 +125,31 @@ pub fn handler(ctx: Context<SendUsdc>, amount: u64, is_emergency: bool) -> Resul
     } else {
         // Agent flow
         require!(!vault.paused, VaultError::VaultPaused);
+        let limits = helpers::agent_limits(vault, &signer_key, ctx.accounts.agent_role.as_deref())?;
 
-        // Check whitelist
-        if let Some(ref wl_entry) = ctx.accounts.whitelist_entry {
+        // Check whitelist; a matching entry replaces tier checks with its own limits
+        if let Some(ref mut wl_entry) = ctx.accounts.whitelist_entry {
             if wl_entry.vault == vault.key()
-                && wl_entry.address == ctx.accounts.recipient_ata.owner
+                && wl_entry.address == recipient_owner
+                && wl_entry.is_active(now)
             {
+                require!(
+                    wl_entry.max_per_tx == 0 || received <= wl_entry.max_per_tx,
+                    VaultError::WhitelistTxLimitExceeded
+                );
+                require!(
+                    wl_entry.period_budget.record(received, now),
+                    VaultError::WhitelistPeriodLimitExceeded
+                );
                 whitelisted = true;
                 tier = 0;
             }
         }
 
         if !whitelisted {
-            if amount <= vault.tier1_max {
+            if received <= limits.tier1_max {
                 tier = 1;
-            } else if amount <= vault.tier2_max {
+            } else if received <= limits.tier2_max {
                 require!(is_emergency, VaultError::NotEmergency);
                 tier = 2;
             } else {
@@ -96,50 +158,68 @@ pub fn handler(ctx: Context<SendUsdc>, amount: u64, is_emergency: bool) -> Resul
         }
     }
 
+    // Agent sends count against the rolling budgets; human overrides don't
+    if !is_human {
+        let vault: &mut Vault = &mut ctx.accounts.vault;
+        helpers::record_budget_spend(&mut vault.daily_budget, &mut vault.weekly_budget, received, now)?;
+    }
+
     // Execute transfer using vault PDA as signer
-    let human_key = vault.human;
-    let agent_key = vault.agent;
+    let vault = &ctx.accounts.vault;
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
     let bump = vault.bump;
     let seeds = &[
         Vault::SEED_PREFIX,
-        human_key.as_ref(),
-        agent_key.as_ref(),
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
         &[bump],
     ];
     let signer_seeds = &[&seeds[..]];
 
     let cpi_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.vault_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
             to: ctx.accounts.recipient_ata.to_account_info(),
             authority: ctx.accounts.vault.to_account_info(),
         },
         signer_seeds,
     );
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
 
     // Calculate and transfer fee
     let fee = helpers::calculate_and_transfer_fee(
-        amount,
+        received,
         ctx.accounts.protocol_config.fee_bps,
         ctx.accounts.protocol_config.total_staked,
+        &ctx.accounts.usdc_mint,
         &ctx.accounts.vault_usdc_ata,
-        &ctx.accounts.staker_reward_ata,
+        Some(&ctx.accounts.staker_reward_ata),
         &ctx.accounts.buyback_ata,
         &ctx.accounts.vault.to_account_info(),
         &ctx.accounts.token_program,
         signer_seeds,
     )?;
 
+    helpers::write_payment_memo(&ctx.accounts.memo_program, &memo, invoice_id.as_deref())?;
+
+    let vault = &mut ctx.accounts.vault;
+    helpers::record_usdc_outflow(vault, amount, fee)?;
+
     emit!(UsdcSent {
         vault: vault.key(),
         signer: signer_key,
-        recipient: ctx.accounts.recipient_ata.owner,
-        amount,
+        recipient: recipient_owner,
+        amount: received,
         fee,
         tier,
         whitelisted,
+        daily_remaining: vault.daily_budget.remaining(now),
+        weekly_remaining: vault.weekly_budget.remaining(now),
+        memo,
+        invoice_id,
     });
 
     Ok(())
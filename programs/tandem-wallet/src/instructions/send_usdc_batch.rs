@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Maximum number of lines in one batch.
+pub const MAX_BATCH_LINES: usize = 16;
+
+/// One validated batch line.
+struct BatchLine<'info> {
+    recipient_ata: InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+    received: u64,
+    tier: u8,
+    whitelisted: bool,
+}
+
+/// Pays several recipients from the vault in one instruction. Each line takes two
+/// remaining accounts: the recipient's USDC token account, then its whitelist entry
+/// (pass the program ID when there is none). Amounts are passed in line order.
+/// One memo, and optionally an invoice ID, covers the whole batch.
+#[derive(Accounts)]
+pub struct SendUsdcBatch<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == vault.vault_usdc_ata,
+    )]
+    pub vault_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Role account when the signer is an additional (non-primary) agent.
+    #[account(
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), signer.key().as_ref()],
+        bump = agent_role.bump,
+    )]
+    pub agent_role: Option<Account<'info, AgentRole>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SendUsdcBatch<'info>>,
+    amounts: Vec<u64>,
+    is_emergency: bool,
+    memo: String,
+    invoice_id: Option<String>,
+) -> Result<()> {
+    require!(
+        !amounts.is_empty()
+            && amounts.len() <= MAX_BATCH_LINES
+            && ctx.remaining_accounts.len() == amounts.len() * 2,
+        VaultError::InvalidBatch
+    );
+    require!(amounts.iter().all(|a| *a > 0), VaultError::ZeroAmount);
+    helpers::check_payment_memo(&memo, invoice_id.as_deref())?;
+
+    let vault = &ctx.accounts.vault;
+    let vault_key = vault.key();
+    let signer_key = ctx.accounts.signer.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    let is_human = signer_key == vault.human;
+    let is_agent = signer_key == vault.agent || ctx.accounts.agent_role.is_some();
+
+    // Must be human or a registered agent
+    require!(is_human || is_agent, VaultError::OnlyAgentOrHuman);
+
+    let limits = if is_human {
+        None
+    } else {
+        require!(!vault.paused, VaultError::VaultPaused);
+        Some(helpers::agent_limits(vault, &signer_key, ctx.accounts.agent_role.as_deref())?)
+    };
+
+    // Validate every line before moving any funds
+    let mut lines: Vec<BatchLine<'info>> = Vec::with_capacity(amounts.len());
+    let mut total: u64 = 0;
+    let mut tiered_total: u64 = 0;
+
+    for (accounts, amount) in ctx.remaining_accounts.chunks(2).zip(amounts.iter()) {
+        let recipient_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[0])?;
+        require_keys_eq!(recipient_ata.mint, vault.usdc_mint, VaultError::InvalidTokenAccount);
+        require!(accounts[0].is_writable, VaultError::InvalidBatch);
+
+        let received = helpers::amount_received(&ctx.accounts.usdc_mint, *amount)?;
+        total = total.checked_add(received).ok_or(VaultError::Overflow)?;
+
+        let mut tier = 0;
+        let mut whitelisted = false;
+        if let Some(ref limits) = limits {
+            // A matching whitelist entry replaces tier checks for its line
+            let wl_info = &accounts[1];
+            if wl_info.key() != crate::ID {
+                let mut wl_entry = Account::<WhitelistEntry>::try_from(wl_info)?;
+                if wl_entry.vault == vault_key
+                    && wl_entry.address == recipient_ata.owner
+                    && wl_entry.is_active(now)
+                {
+                    require!(
+                        wl_entry.max_per_tx == 0 || received <= wl_entry.max_per_tx,
+                        VaultError::WhitelistTxLimitExceeded
+                    );
+                    require!(
+                        wl_entry.period_budget.record(received, now),
+                        VaultError::WhitelistPeriodLimitExceeded
+                    );
+                    // Persist now so a repeated entry sees this line's spend
+                    wl_entry.exit(&crate::ID)?;
+                    whitelisted = true;
+                }
+            }
+
+            if !whitelisted {
+                tier = helpers::agent_tier(received, limits, is_emergency)?;
+                tiered_total = tiered_total.checked_add(received).ok_or(VaultError::Overflow)?;
+            }
+        }
+
+        lines.push(BatchLine {
+            recipient_ata,
+            amount: *amount,
+            received,
+            tier,
+            whitelisted,
+        });
+    }
+
+    // Non-whitelisted lines are also tiered as a whole
+    let mut batch_tier: u8 = 0;
+    if let Some(ref limits) = limits {
+        if tiered_total > 0 {
+            batch_tier = helpers::agent_tier(tiered_total, limits, is_emergency)?;
+        }
+        let vault: &mut Vault = &mut ctx.accounts.vault;
+        helpers::record_budget_spend(&mut vault.daily_budget, &mut vault.weekly_budget, total, now)?;
+    }
+
+    // Execute transfers using vault PDA as signer
+    let vault = &ctx.accounts.vault;
+    let seed_human = vault.seed_human;
+    let seed_agent = vault.seed_agent;
+    let bump = vault.bump;
+    let seeds = &[
+        Vault::SEED_PREFIX,
+        seed_human.as_ref(),
+        seed_agent.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let mut sent: u64 = 0;
+    for (index, line) in lines.iter().enumerate() {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: line.recipient_ata.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, line.amount, ctx.accounts.usdc_mint.decimals)?;
+        sent = sent.checked_add(line.amount).ok_or(VaultError::Overflow)?;
+
+        emit!(BatchLineSent {
+            vault: vault_key,
+            signer: signer_key,
+            index: index as u8,
+            recipient: line.recipient_ata.owner,
+            amount: line.received,
+            tier: line.tier,
+            whitelisted: line.whitelisted,
+        });
+    }
+
+    // One fee on the batch total
+    let fee = helpers::calculate_and_transfer_fee(
+        total,
+        ctx.accounts.protocol_config.fee_bps,
+        ctx.accounts.protocol_config.total_staked,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.vault_usdc_ata,
+        Some(&ctx.accounts.staker_reward_ata),
+        &ctx.accounts.buyback_ata,
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.token_program,
+        signer_seeds,
+    )?;
+
+    helpers::write_payment_memo(&ctx.accounts.memo_program, &memo, invoice_id.as_deref())?;
+
+    let vault = &mut ctx.accounts.vault;
+    helpers::record_usdc_outflow(vault, sent, fee)?;
+
+    emit!(UsdcBatchSent {
+        vault: vault_key,
+        signer: signer_key,
+        lines: lines.len() as u8,
+        total,
+        fee,
+        tier: batch_tier,
+        daily_remaining: vault.daily_budget.remaining(now),
+        weekly_remaining: vault.weekly_budget.remaining(now),
+        memo,
+        invoice_id,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetApprovalThreshold<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<SetApprovalThreshold>, threshold: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    require!(
+        threshold >= 1 && threshold as usize <= vault.approvers.len(),
+        VaultError::InvalidApprovalThreshold
+    );
+
+    vault.approval_threshold = threshold;
+
+    emit!(ApprovalThresholdUpdated {
+        vault: vault.key(),
+        approval_threshold: threshold,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetBudgets<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<SetBudgets>, daily_limit: u64, weekly_limit: u64) -> Result<()> {
+    // Zero disables a budget; when both are set the daily one can't exceed the weekly one
+    require!(
+        daily_limit == 0 || weekly_limit == 0 || daily_limit <= weekly_limit,
+        VaultError::InvalidBudgets
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    vault.daily_budget.limit = daily_limit;
+    vault.weekly_budget.limit = weekly_limit;
+
+    emit!(BudgetsUpdated {
+        vault: vault.key(),
+        daily_limit,
+        weekly_limit,
+    });
+
+    Ok(())
+}
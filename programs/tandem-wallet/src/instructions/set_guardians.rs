@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(
+    ctx: Context<SetGuardians>,
+    guardians: Vec<Pubkey>,
+    guardian_threshold: u8,
+    recovery_delay: i64,
+) -> Result<()> {
+    require!(guardians.len() <= MAX_GUARDIANS, VaultError::InvalidGuardians);
+    require!(recovery_delay >= 0, VaultError::InvalidDelay);
+    for (i, guardian) in guardians.iter().enumerate() {
+        require!(
+            !guardians[..i].contains(guardian),
+            VaultError::InvalidGuardians
+        );
+    }
+    // An empty set disables recovery; otherwise the threshold must be reachable
+    if guardians.is_empty() {
+        require!(guardian_threshold == 0, VaultError::InvalidGuardians);
+    } else {
+        require!(
+            guardian_threshold >= 1 && guardian_threshold as usize <= guardians.len(),
+            VaultError::InvalidGuardians
+        );
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.guardians = guardians;
+    vault.guardian_threshold = guardian_threshold;
+    vault.recovery_delay = recovery_delay;
+
+    emit!(GuardiansUpdated {
+        vault: vault.key(),
+        guardians: vault.guardians.clone(),
+        guardian_threshold,
+        recovery_delay,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetMintLimits<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [MintConfig::SEED_PREFIX, vault.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SetMintLimits>,
+    tier1_max: u64,
+    tier2_max: u64,
+    daily_limit: u64,
+    weekly_limit: u64,
+) -> Result<()> {
+    require!(tier1_max <= tier2_max, VaultError::InvalidThresholds);
+    require!(
+        daily_limit == 0 || weekly_limit == 0 || daily_limit <= weekly_limit,
+        VaultError::InvalidBudgets
+    );
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.tier1_max = tier1_max;
+    mint_config.tier2_max = tier2_max;
+    mint_config.daily_budget.limit = daily_limit;
+    mint_config.weekly_budget.limit = weekly_limit;
+
+    emit!(MintLimitsUpdated {
+        vault: mint_config.vault,
+        mint: mint_config.mint,
+        tier1_max,
+        tier2_max,
+        daily_limit,
+        weekly_limit,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetOptimisticBand<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<SetOptimisticBand>, optimistic_max: u64, veto_period: i64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    if optimistic_max > 0 {
+        require!(
+            optimistic_max > vault.tier2_max && veto_period > 0,
+            VaultError::InvalidOptimisticBand
+        );
+        // Proposals must be able to outlive their veto period
+        require!(
+            vault.proposal_ttl == 0 || veto_period < vault.proposal_ttl,
+            VaultError::InvalidOptimisticBand
+        );
+    }
+    require!(veto_period >= 0, VaultError::InvalidDelay);
+
+    vault.optimistic_max = optimistic_max;
+    vault.veto_period = veto_period;
+
+    emit!(OptimisticBandUpdated {
+        vault: vault.key(),
+        optimistic_max,
+        veto_period,
+    });
+
+    Ok(())
+}
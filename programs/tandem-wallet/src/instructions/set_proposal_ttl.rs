@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetProposalTtl<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<SetProposalTtl>, proposal_ttl: i64) -> Result<()> {
+    require!(proposal_ttl >= 0, VaultError::InvalidDelay);
+
+    let vault = &mut ctx.accounts.vault;
+    // Optimistic proposals must be able to outlive their veto period
+    if vault.optimistic_max > 0 && proposal_ttl > 0 {
+        require!(vault.veto_period < proposal_ttl, VaultError::InvalidOptimisticBand);
+    }
+
+    vault.proposal_ttl = proposal_ttl;
+
+    emit!(ProposalTtlUpdated {
+        vault: vault.key(),
+        proposal_ttl,
+    });
+
+    Ok(())
+}
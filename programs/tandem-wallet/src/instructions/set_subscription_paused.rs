@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetSubscriptionPaused<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            Subscription::SEED_PREFIX,
+            vault.key().as_ref(),
+            subscription.subscription_id.to_le_bytes().as_ref(),
+        ],
+        bump = subscription.bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+}
+
+pub fn handler(ctx: Context<SetSubscriptionPaused>, paused: bool) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.paused = paused;
+
+    // Intervals that passed while paused aren't owed; resume at the next slot
+    if !paused {
+        let now = Clock::get()?.unix_timestamp;
+        subscription.next_payment_at = subscription
+            .slot_at_or_after(now)
+            .ok_or(VaultError::Overflow)?;
+    }
+
+    emit!(SubscriptionPauseUpdated {
+        vault: ctx.accounts.vault.key(),
+        subscription_id: subscription.subscription_id,
+        paused,
+        next_payment_at: subscription.next_payment_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Raising the delay applies at once. Lowering it only applies once the current
+/// delay has passed, so entries added meanwhile still wait the longer delay.
+#[derive(Accounts)]
+pub struct SetWhitelistDelay<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<SetWhitelistDelay>, delay: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault_key = ctx.accounts.vault.key();
+    let vault: &mut Vault = &mut ctx.accounts.vault;
+    let effective_at = helpers::change_delay(
+        &mut vault.whitelist_delay,
+        &mut vault.pending_whitelist_delay,
+        delay,
+        now,
+    )?;
+
+    emit!(WhitelistDelayUpdated {
+        vault: vault_key,
+        delay,
+        effective_at,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::errors::*;
@@ -33,25 +33,30 @@ pub struct Stake<'info> {
         constraint = staker_tandem_ata.mint == protocol_config.tandem_mint,
         constraint = staker_tandem_ata.owner == staker.key(),
     )]
-    pub staker_tandem_ata: Box<Account<'info, TokenAccount>>,
+    pub staker_tandem_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Protocol's TANDEM ATA where staked tokens are held
     #[account(
         mut,
         associated_token::mint = tandem_mint,
         associated_token::authority = protocol_config,
+        associated_token::token_program = token_program,
     )]
-    pub stake_tandem_ata: Box<Account<'info, TokenAccount>>,
+    pub stake_tandem_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Staker reward USDC ATA (for balance check in update_rewards)
     #[account(
         constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
     )]
-    pub staker_reward_ata: Box<Account<'info, TokenAccount>>,
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub tandem_mint: Account<'info, Mint>,
+    #[account(
+        constraint = tandem_mint.key() == protocol_config.tandem_mint,
+    )]
+    pub tandem_mint: Box<InterfaceAccount<'info, Mint>>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    /// Token program owning the TANDEM mint
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -66,34 +71,38 @@ pub fn handler(ctx: Context<Stake>, amount: u64) -> Result<()> {
     // Lazy reward update before state change
     helpers::update_rewards(config, Some(stake_account), reward_balance)?;
 
+    // Stake is credited with what the protocol actually receives
+    let received = helpers::amount_received(&ctx.accounts.tandem_mint, amount)?;
+
     // Transfer TANDEM from staker to protocol
     let cpi_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.staker_tandem_ata.to_account_info(),
+            mint: ctx.accounts.tandem_mint.to_account_info(),
             to: ctx.accounts.stake_tandem_ata.to_account_info(),
             authority: ctx.accounts.staker.to_account_info(),
         },
     );
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.tandem_mint.decimals)?;
 
     // Update stake state
     stake_account.staker = ctx.accounts.staker.key();
     stake_account.staked_amount = stake_account
         .staked_amount
-        .checked_add(amount)
+        .checked_add(received)
         .ok_or(VaultError::Overflow)?;
     stake_account.last_stake_ts = Clock::get()?.unix_timestamp;
     stake_account.bump = ctx.bumps.stake_account;
 
     config.total_staked = config
         .total_staked
-        .checked_add(amount)
+        .checked_add(received)
         .ok_or(VaultError::Overflow)?;
 
     emit!(Staked {
         staker: ctx.accounts.staker.key(),
-        amount,
+        amount: received,
         total_staked: config.total_staked,
     });
 
@@ -9,7 +9,7 @@ pub struct Unpause<'info> {
 
     #[account(
         mut,
-        seeds = [Vault::SEED_PREFIX, vault.human.as_ref(), vault.agent.as_ref()],
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
         bump = vault.bump,
         constraint = vault.human == human.key() @ VaultError::OnlyHuman,
         constraint = vault.paused @ VaultError::VaultPaused,
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::errors::*;
@@ -32,24 +32,29 @@ pub struct Unstake<'info> {
         constraint = staker_tandem_ata.mint == protocol_config.tandem_mint,
         constraint = staker_tandem_ata.owner == staker.key(),
     )]
-    pub staker_tandem_ata: Account<'info, TokenAccount>,
+    pub staker_tandem_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Protocol's TANDEM ATA where staked tokens are held
     #[account(
         mut,
         associated_token::mint = tandem_mint,
         associated_token::authority = protocol_config,
+        associated_token::token_program = token_program,
     )]
-    pub stake_tandem_ata: Account<'info, TokenAccount>,
+    pub stake_tandem_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Staker reward USDC ATA (for balance check in update_rewards)
     #[account(
         constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
     )]
-    pub staker_reward_ata: Account<'info, TokenAccount>,
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub tandem_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        constraint = tandem_mint.key() == protocol_config.tandem_mint,
+    )]
+    pub tandem_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// Token program owning the TANDEM mint
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -72,6 +77,8 @@ pub fn handler(ctx: Context<Unstake>) -> Result<()> {
     let config_info = ctx.accounts.protocol_config.to_account_info();
     let token_info = ctx.accounts.token_program.to_account_info();
     let from_info = ctx.accounts.stake_tandem_ata.to_account_info();
+    let mint_info = ctx.accounts.tandem_mint.to_account_info();
+    let decimals = ctx.accounts.tandem_mint.decimals;
     let to_info = ctx.accounts.staker_tandem_ata.to_account_info();
 
     let config = &mut ctx.accounts.protocol_config;
@@ -86,14 +93,15 @@ pub fn handler(ctx: Context<Unstake>) -> Result<()> {
 
     let cpi_ctx = CpiContext::new_with_signer(
         token_info,
-        Transfer {
+        TransferChecked {
             from: from_info,
+            mint: mint_info,
             to: to_info,
             authority: config_info,
         },
         signer_seeds,
     );
-    token::transfer(cpi_ctx, unstake_amount)?;
+    token_interface::transfer_checked(cpi_ctx, unstake_amount, decimals)?;
 
     // Update state
     stake_account.staked_amount = 0;
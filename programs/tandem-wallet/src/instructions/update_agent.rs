@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateAgent<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [AgentRole::SEED_PREFIX, vault.key().as_ref(), agent_role.agent.as_ref()],
+        bump = agent_role.bump,
+        constraint = agent_role.vault == vault.key(),
+    )]
+    pub agent_role: Account<'info, AgentRole>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateAgent>,
+    tier1_max: u64,
+    tier2_max: u64,
+    paused: bool,
+    can_propose: bool,
+) -> Result<()> {
+    require!(tier1_max <= tier2_max, VaultError::InvalidThresholds);
+
+    let role = &mut ctx.accounts.agent_role;
+    role.tier1_max = tier1_max;
+    role.tier2_max = tier2_max;
+    role.paused = paused;
+    role.can_propose = can_propose;
+
+    emit!(AgentUpdated {
+        vault: role.vault,
+        agent: role.agent,
+        tier1_max,
+        tier2_max,
+        paused,
+        can_propose,
+    });
+
+    Ok(())
+}
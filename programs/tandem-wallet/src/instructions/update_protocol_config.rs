@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount;
 use crate::state::*;
 use crate::errors::*;
 
@@ -19,7 +19,7 @@ pub struct UpdateProtocolConfig<'info> {
     #[account(
         constraint = buyback_ata.mint == protocol_config.usdc_mint,
     )]
-    pub buyback_ata: Account<'info, TokenAccount>,
+    pub buyback_ata: InterfaceAccount<'info, TokenAccount>,
 }
 
 pub fn handler(ctx: Context<UpdateProtocolConfig>, fee_bps: u16) -> Result<()> {
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+#[derive(Accounts)]
+pub struct UpdateWhitelist<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [WhitelistEntry::SEED_PREFIX, vault.key().as_ref(), whitelist_entry.address.as_ref()],
+        bump = whitelist_entry.bump,
+        constraint = whitelist_entry.vault == vault.key(),
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+}
+
+pub fn handler(ctx: Context<UpdateWhitelist>, limits: WhitelistLimits) -> Result<()> {
+    let wl = &mut ctx.accounts.whitelist_entry;
+
+    // Loosening an entry goes through the same activation delay as adding one
+    if helpers::loosens_whitelist_limits(wl, &limits) {
+        let now = Clock::get()?.unix_timestamp;
+        let active_from = now
+            .checked_add(ctx.accounts.vault.whitelist_delay_at(now))
+            .ok_or(VaultError::Overflow)?;
+        wl.active_from = wl.active_from.max(active_from);
+    }
+    helpers::apply_whitelist_limits(wl, &limits)?;
+
+    emit!(WhitelistUpdated {
+        vault: ctx.accounts.vault.key(),
+        address: wl.address,
+        max_per_tx: limits.max_per_tx,
+        max_per_period: limits.max_per_period,
+        period_seconds: limits.period_seconds,
+        active_from: wl.active_from,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    pub human: Signer<'info>,
+
+    #[account(
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+        constraint = vault.human == human.key() @ VaultError::OnlyHuman,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [RecoveryRequest::SEED_PREFIX, vault.key().as_ref()],
+        bump = recovery_request.bump,
+        constraint = recovery_request.vault == vault.key(),
+        close = initiator,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// CHECK: Receives the rent; must be the guardian that opened the request
+    #[account(
+        mut,
+        constraint = initiator.key() == recovery_request.initiator,
+    )]
+    pub initiator: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<VetoRecovery>) -> Result<()> {
+    emit!(RecoveryVetoed {
+        vault: ctx.accounts.vault.key(),
+        new_human: ctx.accounts.recovery_request.new_human,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Lets the proposing agent take back a pending proposal, closing it and
+/// reclaiming its rent in one step.
+#[derive(Accounts)]
+pub struct WithdrawProposal<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            Proposal::SEED_PREFIX,
+            vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.vault == vault.key(),
+        constraint = proposal.proposer == agent.key() @ VaultError::OnlyProposer,
+        constraint = !proposal.executed @ VaultError::ProposalAlreadyExecuted,
+        constraint = !proposal.cancelled @ VaultError::ProposalAlreadyCancelled,
+        close = agent,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The proposer's ledger; required for nonce-keyed proposals.
+    #[account(
+        mut,
+        seeds = [AgentLedger::SEED_PREFIX, vault.key().as_ref(), proposal.proposer.as_ref()],
+        bump = agent_ledger.bump,
+    )]
+    pub agent_ledger: Option<Account<'info, AgentLedger>>,
+}
+
+pub fn handler(ctx: Context<WithdrawProposal>) -> Result<()> {
+    helpers::record_proposal_closed(
+        &mut ctx.accounts.vault,
+        ctx.accounts.agent_ledger.as_deref_mut(),
+        &ctx.accounts.proposal,
+    )?;
+
+    emit!(ProposalWithdrawn {
+        vault: ctx.accounts.vault.key(),
+        proposal_id: ctx.accounts.proposal.proposal_id,
+        proposer: ctx.accounts.agent.key(),
+    });
+
+    Ok(())
+}
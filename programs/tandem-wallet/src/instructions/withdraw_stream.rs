@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::helpers;
+
+/// Pays the recipient everything vested since their last withdrawal.
+/// The protocol fee comes out of the reserve prefunded into the stream, so the
+/// vault's own balance is never touched.
+#[derive(Accounts)]
+pub struct WithdrawStream<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Vault::SEED_PREFIX, vault.seed_human.as_ref(), vault.seed_agent.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        seeds = [Stream::SEED_PREFIX, vault.key().as_ref(), stream.stream_id.to_le_bytes().as_ref()],
+        bump = stream.bump,
+        constraint = stream.recipient == recipient.key() @ VaultError::OnlyStreamRecipient,
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+
+    #[account(
+        constraint = usdc_mint.key() == vault.usdc_mint,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = stream_ata.key() == stream.stream_ata,
+    )]
+    pub stream_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_ata.key() == stream.recipient_ata,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config for fee calculation
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Staker reward USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = staker_reward_ata.key() == protocol_config.staker_reward_ata,
+    )]
+    pub staker_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyback USDC ATA (receives 50% of fee)
+    #[account(
+        mut,
+        constraint = buyback_ata.key() == protocol_config.buyback_ata,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<WithdrawStream>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stream = &ctx.accounts.stream;
+    let amount = stream.vested(now).saturating_sub(stream.withdrawn);
+    require!(amount > 0, VaultError::NothingVested);
+    let received = helpers::amount_received(&ctx.accounts.usdc_mint, amount)?;
+
+    // Pay out using the stream PDA as signer
+    let vault_key = ctx.accounts.vault.key();
+    let stream_id = stream.stream_id.to_le_bytes();
+    let stream_bump = stream.bump;
+    let stream_seeds = &[
+        Stream::SEED_PREFIX,
+        vault_key.as_ref(),
+        stream_id.as_ref(),
+        &[stream_bump],
+    ];
+    let stream_signer = &[&stream_seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.stream_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.recipient_ata.to_account_info(),
+            authority: ctx.accounts.stream.to_account_info(),
+        },
+        stream_signer,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+
+    // Fee comes out of the stream's reserve, at the rate locked in at creation
+    let fee = helpers::calculate_and_transfer_fee(
+        received,
+        ctx.accounts.stream.fee_bps,
+        ctx.accounts.protocol_config.total_staked,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.stream_ata,
+        Some(&ctx.accounts.staker_reward_ata),
+        &ctx.accounts.buyback_ata,
+        &ctx.accounts.stream.to_account_info(),
+        &ctx.accounts.token_program,
+        stream_signer,
+    )?;
+
+    // The reserve was counted as sent; what's charged now counts as fees
+    let vault: &mut Vault = &mut ctx.accounts.vault;
+    vault.total_sent = vault.total_sent.checked_sub(fee).ok_or(VaultError::Overflow)?;
+    helpers::record_usdc_outflow(vault, 0, fee)?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.withdrawn = stream.withdrawn.checked_add(amount).ok_or(VaultError::Overflow)?;
+    stream.fee_reserve = stream.fee_reserve.saturating_sub(fee);
+
+    emit!(StreamWithdrawn {
+        vault: vault_key,
+        stream_id: stream.stream_id,
+        recipient: stream.recipient,
+        amount: received,
+        fee,
+        withdrawn: stream.withdrawn,
+    });
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{CancelReason, WhitelistLimits};
 
 declare_id!("6L2hon3xSV9saeaGG7cgFG298JGW4vf9jDtF5xg8E6pZ");
 
@@ -18,38 +19,339 @@ pub mod tandem_wallet {
         instructions::initialize::handler(ctx, tier1_max, tier2_max)
     }
 
-    pub fn send_usdc(ctx: Context<SendUsdc>, amount: u64, is_emergency: bool) -> Result<()> {
-        instructions::send_usdc::handler(ctx, amount, is_emergency)
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, reference: Option<String>) -> Result<()> {
+        instructions::deposit::handler(ctx, amount, reference)
+    }
+
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<()> {
+        instructions::reconcile::handler(ctx)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        instructions::withdraw::handler(ctx, amount)
+    }
+
+    pub fn close_vault<'info>(ctx: Context<'_, '_, 'info, 'info, CloseVault<'info>>) -> Result<()> {
+        instructions::close_vault::handler(ctx)
+    }
+
+    pub fn send_usdc(
+        ctx: Context<SendUsdc>,
+        amount: u64,
+        is_emergency: bool,
+        memo: String,
+        invoice_id: Option<String>,
+    ) -> Result<()> {
+        instructions::send_usdc::handler(ctx, amount, is_emergency, memo, invoice_id)
+    }
+
+    pub fn send_usdc_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendUsdcBatch<'info>>,
+        amounts: Vec<u64>,
+        is_emergency: bool,
+        memo: String,
+        invoice_id: Option<String>,
+    ) -> Result<()> {
+        instructions::send_usdc_batch::handler(ctx, amounts, is_emergency, memo, invoice_id)
+    }
+
+    pub fn send_token(
+        ctx: Context<SendToken>,
+        amount: u64,
+        is_emergency: bool,
+        memo: String,
+        invoice_id: Option<String>,
+    ) -> Result<()> {
+        instructions::send_token::handler(ctx, amount, is_emergency, memo, invoice_id)
     }
 
     pub fn propose(ctx: Context<Propose>, amount: u64, memo: String) -> Result<()> {
         instructions::propose::handler(ctx, amount, memo)
     }
 
-    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
-        instructions::approve_proposal::handler(ctx)
+    pub fn propose_with_nonce(
+        ctx: Context<ProposeWithNonce>,
+        nonce: u64,
+        amount: u64,
+        memo: String,
+    ) -> Result<()> {
+        instructions::propose_with_nonce::handler(ctx, nonce, amount, memo)
+    }
+
+    pub fn open_agent_ledger(ctx: Context<OpenAgentLedger>) -> Result<()> {
+        instructions::open_agent_ledger::handler(ctx)
+    }
+
+    pub fn approve_proposal(
+        ctx: Context<ApproveProposal>,
+        revision: u32,
+        requested_amount: u64,
+        approved_amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::approve_proposal::handler(ctx, revision, requested_amount, approved_amount)
+    }
+
+    pub fn approve_proposals<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApproveProposals<'info>>,
+        revisions: Vec<u32>,
+        requested_amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::approve_proposals::handler(ctx, revisions, requested_amounts)
+    }
+
+    pub fn cancel_proposals<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelProposals<'info>>,
+        reason: CancelReason,
+        note: String,
+    ) -> Result<()> {
+        instructions::cancel_proposals::handler(ctx, reason, note)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        instructions::execute_proposal::handler(ctx)
+    }
+
+    pub fn cancel_proposal(
+        ctx: Context<CancelProposal>,
+        reason: CancelReason,
+        note: String,
+    ) -> Result<()> {
+        instructions::cancel_proposal::handler(ctx, reason, note)
+    }
+
+    pub fn amend_proposal(
+        ctx: Context<AmendProposal>,
+        amount: Option<u64>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::amend_proposal::handler(ctx, amount, memo)
     }
 
-    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
-        instructions::cancel_proposal::handler(ctx)
+    pub fn withdraw_proposal(ctx: Context<WithdrawProposal>) -> Result<()> {
+        instructions::withdraw_proposal::handler(ctx)
     }
 
     pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
         instructions::close_proposal::handler(ctx)
     }
 
+    pub fn close_expired_proposal(ctx: Context<CloseExpiredProposal>) -> Result<()> {
+        instructions::close_expired_proposal::handler(ctx)
+    }
+
+    pub fn set_proposal_ttl(ctx: Context<SetProposalTtl>, proposal_ttl: i64) -> Result<()> {
+        instructions::set_proposal_ttl::handler(ctx, proposal_ttl)
+    }
+
+    pub fn set_execution_delay(ctx: Context<SetExecutionDelay>, execution_delay: i64) -> Result<()> {
+        instructions::set_execution_delay::handler(ctx, execution_delay)
+    }
+
+    pub fn set_optimistic_band(
+        ctx: Context<SetOptimisticBand>,
+        optimistic_max: u64,
+        veto_period: i64,
+    ) -> Result<()> {
+        instructions::set_optimistic_band::handler(ctx, optimistic_max, veto_period)
+    }
+
     pub fn set_tiers(ctx: Context<SetTiers>, tier1_max: u64, tier2_max: u64) -> Result<()> {
         instructions::set_tiers::handler(ctx, tier1_max, tier2_max)
     }
 
-    pub fn add_whitelist(ctx: Context<AddWhitelist>, address: Pubkey) -> Result<()> {
-        instructions::add_whitelist::handler(ctx, address)
+    pub fn set_budgets(ctx: Context<SetBudgets>, daily_limit: u64, weekly_limit: u64) -> Result<()> {
+        instructions::set_budgets::handler(ctx, daily_limit, weekly_limit)
+    }
+
+    pub fn add_mint(ctx: Context<AddMint>, tier1_max: u64, tier2_max: u64) -> Result<()> {
+        instructions::add_mint::handler(ctx, tier1_max, tier2_max)
+    }
+
+    pub fn set_mint_limits(
+        ctx: Context<SetMintLimits>,
+        tier1_max: u64,
+        tier2_max: u64,
+        daily_limit: u64,
+        weekly_limit: u64,
+    ) -> Result<()> {
+        instructions::set_mint_limits::handler(ctx, tier1_max, tier2_max, daily_limit, weekly_limit)
+    }
+
+    pub fn remove_mint(ctx: Context<RemoveMint>) -> Result<()> {
+        instructions::remove_mint::handler(ctx)
+    }
+
+    pub fn add_whitelist(
+        ctx: Context<AddWhitelist>,
+        address: Pubkey,
+        limits: WhitelistLimits,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::add_whitelist::handler(ctx, address, limits, expires_at)
+    }
+
+    pub fn update_whitelist(ctx: Context<UpdateWhitelist>, limits: WhitelistLimits) -> Result<()> {
+        instructions::update_whitelist::handler(ctx, limits)
     }
 
     pub fn remove_whitelist(ctx: Context<RemoveWhitelist>) -> Result<()> {
         instructions::remove_whitelist::handler(ctx)
     }
 
+    pub fn cancel_whitelist(ctx: Context<CancelWhitelist>) -> Result<()> {
+        instructions::cancel_whitelist::handler(ctx)
+    }
+
+    pub fn set_whitelist_delay(ctx: Context<SetWhitelistDelay>, delay: i64) -> Result<()> {
+        instructions::set_whitelist_delay::handler(ctx, delay)
+    }
+
+    pub fn add_agent(
+        ctx: Context<AddAgent>,
+        agent: Pubkey,
+        tier1_max: u64,
+        tier2_max: u64,
+        can_propose: bool,
+    ) -> Result<()> {
+        instructions::add_agent::handler(ctx, agent, tier1_max, tier2_max, can_propose)
+    }
+
+    pub fn update_agent(
+        ctx: Context<UpdateAgent>,
+        tier1_max: u64,
+        tier2_max: u64,
+        paused: bool,
+        can_propose: bool,
+    ) -> Result<()> {
+        instructions::update_agent::handler(ctx, tier1_max, tier2_max, paused, can_propose)
+    }
+
+    pub fn remove_agent(ctx: Context<RemoveAgent>) -> Result<()> {
+        instructions::remove_agent::handler(ctx)
+    }
+
+    pub fn rotate_agent(ctx: Context<RotateAgent>, new_agent: Pubkey) -> Result<()> {
+        instructions::rotate_agent::handler(ctx, new_agent)
+    }
+
+    pub fn migrate_vault<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateVault<'info>>) -> Result<()> {
+        instructions::migrate_vault::handler(ctx)
+    }
+
+    pub fn migrate_vault_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateVaultAccounts<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_vault_accounts::handler(ctx)
+    }
+
+    pub fn propose_human_transfer(ctx: Context<ProposeHumanTransfer>, new_human: Pubkey) -> Result<()> {
+        instructions::propose_human_transfer::handler(ctx, new_human)
+    }
+
+    pub fn cancel_human_transfer(ctx: Context<CancelHumanTransfer>) -> Result<()> {
+        instructions::cancel_human_transfer::handler(ctx)
+    }
+
+    pub fn accept_human_transfer(ctx: Context<AcceptHumanTransfer>) -> Result<()> {
+        instructions::accept_human_transfer::handler(ctx)
+    }
+
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        guardian_threshold: u8,
+        recovery_delay: i64,
+    ) -> Result<()> {
+        instructions::set_guardians::handler(ctx, guardians, guardian_threshold, recovery_delay)
+    }
+
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>, new_human: Pubkey) -> Result<()> {
+        instructions::initiate_recovery::handler(ctx, new_human)
+    }
+
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        instructions::approve_recovery::handler(ctx)
+    }
+
+    pub fn veto_recovery(ctx: Context<VetoRecovery>) -> Result<()> {
+        instructions::veto_recovery::handler(ctx)
+    }
+
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        instructions::execute_recovery::handler(ctx)
+    }
+
+    pub fn add_approver(ctx: Context<AddApprover>, approver: Pubkey) -> Result<()> {
+        instructions::add_approver::handler(ctx, approver)
+    }
+
+    pub fn remove_approver(ctx: Context<RemoveApprover>, approver: Pubkey) -> Result<()> {
+        instructions::remove_approver::handler(ctx, approver)
+    }
+
+    pub fn set_approval_threshold(ctx: Context<SetApprovalThreshold>, threshold: u8) -> Result<()> {
+        instructions::set_approval_threshold::handler(ctx, threshold)
+    }
+
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        amount: u64,
+        interval: i64,
+        start_at: i64,
+        end_at: Option<i64>,
+        max_payments: Option<u32>,
+    ) -> Result<()> {
+        instructions::create_subscription::handler(ctx, amount, interval, start_at, end_at, max_payments)
+    }
+
+    pub fn pay_subscription(ctx: Context<PaySubscription>) -> Result<()> {
+        instructions::pay_subscription::handler(ctx)
+    }
+
+    pub fn set_subscription_paused(ctx: Context<SetSubscriptionPaused>, paused: bool) -> Result<()> {
+        instructions::set_subscription_paused::handler(ctx, paused)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::cancel_subscription::handler(ctx)
+    }
+
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        rate_per_second: u64,
+        start_at: i64,
+        end_at: i64,
+        is_emergency: bool,
+    ) -> Result<()> {
+        instructions::create_stream::handler(ctx, rate_per_second, start_at, end_at, is_emergency)
+    }
+
+    pub fn withdraw_stream(ctx: Context<WithdrawStream>) -> Result<()> {
+        instructions::withdraw_stream::handler(ctx)
+    }
+
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        instructions::cancel_stream::handler(ctx)
+    }
+
+    pub fn create_escrow(
+        ctx: Context<CreateEscrow>,
+        amount: u64,
+        deadline: i64,
+        arbiter: Option<Pubkey>,
+        is_emergency: bool,
+    ) -> Result<()> {
+        instructions::create_escrow::handler(ctx, amount, deadline, arbiter, is_emergency)
+    }
+
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        instructions::release_escrow::handler(ctx)
+    }
+
+    pub fn refund_escrow(ctx: Context<RefundEscrow>) -> Result<()> {
+        instructions::refund_escrow::handler(ctx)
+    }
+
     pub fn pause(ctx: Context<Pause>) -> Result<()> {
         instructions::pause::handler(ctx)
     }
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Per-agent bookkeeping for nonce-keyed proposals, so `propose_with_nonce`
+/// can count them without writing the vault.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentLedger {
+    /// The vault the proposals belong to.
+    pub vault: Pubkey,
+    /// The agent whose proposals are counted here.
+    pub agent: Pubkey,
+    /// Nonce-keyed proposals by this agent not yet closed. The vault can't close until they are.
+    pub open_proposals: u32,
+    /// Nonce-keyed proposals this agent has ever created; numbers them for display.
+    pub proposal_count: u64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl AgentLedger {
+    pub const SEED_PREFIX: &'static [u8] = b"agent_ledger";
+}
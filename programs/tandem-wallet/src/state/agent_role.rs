@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct AgentRole {
+    /// The vault this agent can act on.
+    pub vault: Pubkey,
+    /// The additional agent's signing key.
+    pub agent: Pubkey,
+    /// Maximum amount (in USDC minor units) this agent can send per tx at tier 1.
+    pub tier1_max: u64,
+    /// Maximum amount (in USDC minor units) this agent can send per tx at tier 2.
+    pub tier2_max: u64,
+    /// Whether this agent is paused. The vault-wide pause applies as well.
+    pub paused: bool,
+    /// Whether this agent can create proposals.
+    pub can_propose: bool,
+    /// Unix timestamp when the agent was added.
+    pub added_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl AgentRole {
+    pub const SEED_PREFIX: &'static [u8] = b"agent_role";
+}
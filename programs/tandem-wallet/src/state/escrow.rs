@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    /// The vault that funded this escrow.
+    pub vault: Pubkey,
+    /// Unique escrow ID (sequential per vault).
+    pub escrow_id: u64,
+    /// The human or agent that created the escrow and paid its rent.
+    pub payer: Pubkey,
+    /// The payee wallet address.
+    pub payee: Pubkey,
+    /// The payee's USDC token account.
+    pub payee_ata: Pubkey,
+    /// The escrow's own USDC token account holding the locked funds.
+    pub escrow_ata: Pubkey,
+    /// Optional key that can release or refund at any time to settle a dispute.
+    pub arbiter: Option<Pubkey>,
+    /// Amount locked (in USDC minor units).
+    pub amount: u64,
+    /// Protocol fee rate locked in when the escrow was created.
+    pub fee_bps: u16,
+    /// Protocol fee prefunded into the escrow on top of `amount`.
+    pub fee_reserve: u64,
+    /// Unix timestamp after which the payer can refund.
+    pub deadline: i64,
+    /// Unix timestamp when the escrow was created.
+    pub created_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Escrow {
+    pub const SEED_PREFIX: &'static [u8] = b"escrow";
+
+    pub fn is_arbiter(&self, key: &Pubkey) -> bool {
+        self.arbiter.as_ref() == Some(key)
+    }
+}
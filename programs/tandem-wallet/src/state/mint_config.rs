@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use super::SpendWindow;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    /// The vault this mint is enabled on.
+    pub vault: Pubkey,
+    /// The SPL mint.
+    pub mint: Pubkey,
+    /// The vault's associated token account for this mint.
+    pub vault_ata: Pubkey,
+    /// Maximum amount (in the mint's minor units) an agent can send per tx at tier 1.
+    pub tier1_max: u64,
+    /// Maximum amount (in the mint's minor units) an agent can send per tx at tier 2.
+    pub tier2_max: u64,
+    /// Rolling 24h budget for agent sends of this mint.
+    pub daily_budget: SpendWindow,
+    /// Rolling 7-day budget for agent sends of this mint.
+    pub weekly_budget: SpendWindow,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl MintConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"mint_config";
+}
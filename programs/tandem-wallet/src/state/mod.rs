@@ -3,9 +3,27 @@ pub mod proposal;
 pub mod whitelist_entry;
 pub mod protocol_config;
 pub mod stake_account;
+pub mod spend_window;
+pub mod pending_delay;
+pub mod agent_role;
+pub mod agent_ledger;
+pub mod recovery_request;
+pub mod mint_config;
+pub mod subscription;
+pub mod stream;
+pub mod escrow;
 
 pub use vault::*;
 pub use proposal::*;
 pub use whitelist_entry::*;
 pub use protocol_config::*;
 pub use stake_account::*;
+pub use spend_window::*;
+pub use pending_delay::*;
+pub use agent_role::*;
+pub use agent_ledger::*;
+pub use recovery_request::*;
+pub use mint_config::*;
+pub use subscription::*;
+pub use stream::*;
+pub use escrow::*;
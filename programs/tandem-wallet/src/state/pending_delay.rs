@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// A shorter delay the human has asked for. It only takes effect once the delay
+/// it replaces has run out, so a stolen key can't lower a delay and act at once.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct PendingDelay {
+    /// The new, shorter delay in seconds.
+    pub delay: i64,
+    /// Unix timestamp from which `delay` applies.
+    pub effective_at: i64,
+}
+
+impl PendingDelay {
+    /// The delay in force at `now`: the pending one once it has matured, otherwise `current`.
+    pub fn resolve(current: i64, pending: Option<PendingDelay>, now: i64) -> i64 {
+        match pending {
+            Some(pending) if now >= pending.effective_at => pending.delay,
+            _ => current,
+        }
+    }
+}
@@ -1,31 +1,140 @@
 use anchor_lang::prelude::*;
+use super::MAX_APPROVERS;
+
+/// Maximum length of a payment memo, in bytes.
+pub const MAX_MEMO_LEN: usize = 128;
+
+/// Maximum length of an invoice ID attached to a send, in bytes.
+pub const MAX_INVOICE_ID_LEN: usize = 64;
+
+/// Maximum length of the note left when cancelling a proposal, in bytes.
+pub const MAX_CANCEL_NOTE_LEN: usize = 128;
+
+/// Why a proposal was rejected, so the agent can adjust before proposing again.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CancelReason {
+    Other,
+    WrongRecipient,
+    AmountTooHigh,
+    NotNeeded,
+    Suspicious,
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct Proposal {
     /// The vault this proposal belongs to.
     pub vault: Pubkey,
-    /// Unique proposal ID (sequential per vault).
+    /// Proposal ID: sequential per vault, or the agent's nonce (top bit set)
+    /// for proposals created with `propose_with_nonce`. A nonce is free again
+    /// once its proposal is withdrawn or closed, so the same ID can later name
+    /// a different payment; `sequence` tells them apart.
     pub proposal_id: u64,
+    /// Number to show the proposal by: `proposal_id` for sequential proposals,
+    /// or the proposer's running count from its `AgentLedger` for nonce-keyed
+    /// ones, which is never reused.
+    pub sequence: u64,
+    /// The agent that created the proposal and paid its rent.
+    pub proposer: Pubkey,
     /// The recipient wallet address.
     pub recipient: Pubkey,
-    /// The recipient's associated token account for USDC.
+    /// The mint to pay out: the vault's USDC mint or one enabled through `MintConfig`.
+    pub mint: Pubkey,
+    /// The recipient's associated token account for `mint`.
     pub recipient_ata: Pubkey,
-    /// Amount of USDC (minor units) requested.
+    /// Amount (in the mint's minor units) requested.
     pub amount: u64,
+    /// Amount actually paid out on approval, at most `amount`; `None` until executed.
+    pub approved_amount: Option<u64>,
     /// Unix timestamp when the proposal was created.
     pub proposed_at: i64,
+    /// Unix timestamp after which the proposal can no longer be approved.
+    pub expires_at: Option<i64>,
+    /// Unix timestamp when the approval threshold was met.
+    pub approved_at: Option<i64>,
+    /// Unix timestamp from which a scheduled proposal can be executed; set on
+    /// approval when the vault has an execution delay, or on creation for
+    /// optimistic proposals.
+    pub executable_at: Option<i64>,
+    /// Whether the proposal fell in the vault's optimistic band and can be
+    /// executed without approval once its veto period passes.
+    pub optimistic: bool,
     /// Whether this proposal has been executed.
     pub executed: bool,
     /// Whether this proposal has been cancelled.
     pub cancelled: bool,
+    /// Reason given by whoever cancelled the proposal; `None` until cancelled.
+    pub cancel_reason: Option<CancelReason>,
+    /// Free-form note left with the cancellation, for the agent to read back.
+    #[max_len(MAX_CANCEL_NOTE_LEN)]
+    pub cancel_note: String,
+    /// Approvers who have voted for this proposal.
+    #[max_len(MAX_APPROVERS)]
+    pub approvals: Vec<Pubkey>,
+    /// Amount each approver voted to pay, in the same order as `approvals`.
+    #[max_len(MAX_APPROVERS)]
+    pub approval_amounts: Vec<u64>,
     /// Human-readable memo describing the purpose.
-    #[max_len(128)]
+    #[max_len(MAX_MEMO_LEN)]
     pub memo: String,
+    /// Bumped on every `amend_proposal`; approvals name the revision they approve.
+    pub revision: u32,
     /// PDA bump seed.
     pub bump: u8,
 }
 
 impl Proposal {
     pub const SEED_PREFIX: &'static [u8] = b"proposal";
+    /// Set on every client-chosen nonce, keeping them apart from sequential IDs.
+    pub const NONCE_FLAG: u64 = 1 << 63;
+
+    /// Whether the proposal is keyed by an agent nonce rather than the vault's counter.
+    pub fn is_nonce_keyed(&self) -> bool {
+        self.proposal_id & Self::NONCE_FLAG != 0
+    }
+
+    /// Whether the proposal has a time from which anyone can execute it.
+    pub fn is_scheduled(&self) -> bool {
+        self.executable_at.is_some()
+    }
+
+    /// Whether the proposal is scheduled but `executable_at` hasn't come yet.
+    pub fn is_awaiting_execution(&self, now: i64) -> bool {
+        self.executable_at.is_some_and(|executable_at| now < executable_at)
+    }
+
+    /// The smallest amount any recorded approver voted to pay.
+    pub fn min_approved_amount(&self) -> Option<u64> {
+        self.approval_amounts.iter().copied().min()
+    }
+
+    /// Whether the proposal can no longer be approved. Approved proposals don't expire.
+    pub fn is_expired(&self, now: i64) -> bool {
+        if self.approved_at.is_some() {
+            return false;
+        }
+        match self.expires_at {
+            Some(expires_at) => now >= expires_at,
+            None => false,
+        }
+    }
+}
+
+/// Proposal layout before expiry and approver votes. Only read by `migrate_vault`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyProposal {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub recipient: Pubkey,
+    pub recipient_ata: Pubkey,
+    pub amount: u64,
+    pub proposed_at: i64,
+    pub executed: bool,
+    pub cancelled: bool,
+    pub memo: String,
+    pub bump: u8,
+}
+
+impl LegacyProposal {
+    pub const SPACE: usize = 32 + 8 + 32 + 32 + 8 + 8 + 1 + 1 + (4 + 128) + 1;
 }
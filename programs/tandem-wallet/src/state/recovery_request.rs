@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use super::MAX_GUARDIANS;
+
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    /// The vault being recovered.
+    pub vault: Pubkey,
+    /// The human key the guardians want to appoint.
+    pub new_human: Pubkey,
+    /// The guardian that opened the request and paid its rent.
+    pub initiator: Pubkey,
+    /// Guardians who have voted for this request.
+    #[max_len(MAX_GUARDIANS)]
+    pub approvals: Vec<Pubkey>,
+    /// Unix timestamp when the request was opened.
+    pub initiated_at: i64,
+    /// Unix timestamp after which the recovery can execute. Set once the threshold is met.
+    pub executable_at: Option<i64>,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl RecoveryRequest {
+    pub const SEED_PREFIX: &'static [u8] = b"recovery";
+}
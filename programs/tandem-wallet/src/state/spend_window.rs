@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+/// Sliding spend budget over the last `period` seconds. Spending is kept in two
+/// consecutive buckets of `period` seconds; the previous bucket counts in
+/// proportion to how much of it still overlaps the window ending now, so the
+/// allowance frees up gradually instead of all at once at a boundary.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct SpendWindow {
+    /// Maximum amount (in minor units) spendable per window. Zero means no limit.
+    pub limit: u64,
+    /// Length of the window in seconds.
+    pub period: i64,
+    /// Amount spent in the current bucket.
+    pub spent: u64,
+    /// Unix timestamp when the current bucket started.
+    pub window_start: i64,
+    /// Amount spent in the bucket before the current one.
+    pub previous_spent: u64,
+}
+
+impl SpendWindow {
+    pub const DAY_SECONDS: i64 = 24 * 60 * 60;
+    pub const WEEK_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+    pub fn new(limit: u64, period: i64) -> Self {
+        Self {
+            limit,
+            period,
+            spent: 0,
+            window_start: 0,
+            previous_spent: 0,
+        }
+    }
+
+    /// Move the buckets forward so the current one contains `now`.
+    fn advance(&mut self, now: i64) {
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed < self.period {
+            return;
+        }
+        if elapsed < self.period.saturating_mul(2) {
+            self.previous_spent = self.spent;
+            self.window_start = self.window_start.saturating_add(self.period);
+        } else {
+            self.previous_spent = 0;
+            self.window_start = now;
+        }
+        self.spent = 0;
+    }
+
+    /// Spending counted against the window ending at `now`; buckets must be current.
+    fn weighted_spent(&self, now: i64) -> u64 {
+        if self.period <= 0 || self.previous_spent == 0 {
+            return self.spent;
+        }
+        let overlap = self.period.saturating_sub(now.saturating_sub(self.window_start)).max(0);
+        let carried = (self.previous_spent as u128) * (overlap as u128) / (self.period as u128);
+        self.spent.saturating_add(carried as u64)
+    }
+
+    /// Amount still spendable at `now`. Returns `u64::MAX` when unlimited.
+    pub fn remaining(&self, now: i64) -> u64 {
+        if self.limit == 0 {
+            return u64::MAX;
+        }
+        let mut window = *self;
+        window.advance(now);
+        self.limit.saturating_sub(window.weighted_spent(now))
+    }
+
+    /// Record `amount` against the window. Returns false if it would exceed the limit.
+    pub fn record(&mut self, amount: u64, now: i64) -> bool {
+        self.advance(now);
+        let spent = match self.spent.checked_add(amount) {
+            Some(spent) => spent,
+            None => return false,
+        };
+        if self.limit > 0 && self.weighted_spent(now).saturating_add(amount) > self.limit {
+            return false;
+        }
+        self.spent = spent;
+        true
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    /// The vault that funded this stream.
+    pub vault: Pubkey,
+    /// Unique stream ID (sequential per vault).
+    pub stream_id: u64,
+    /// The human or agent that created the stream and paid its rent.
+    pub creator: Pubkey,
+    /// The recipient wallet address.
+    pub recipient: Pubkey,
+    /// The recipient's USDC token account.
+    pub recipient_ata: Pubkey,
+    /// The stream's own USDC token account holding unwithdrawn funds.
+    pub stream_ata: Pubkey,
+    /// Amount (in USDC minor units) vesting per second.
+    pub rate_per_second: u64,
+    /// Unix timestamp when vesting starts.
+    pub start_at: i64,
+    /// Unix timestamp when vesting ends.
+    pub end_at: i64,
+    /// Total amount moved from the vault into the stream.
+    pub deposited: u64,
+    /// Amount the recipient has withdrawn so far.
+    pub withdrawn: u64,
+    /// Protocol fee rate at creation. The fee on every payout is charged at this rate.
+    pub fee_bps: u16,
+    /// Fee prefunded into `stream_ata` at creation and not yet charged, so
+    /// payouts never draw on the vault.
+    pub fee_reserve: u64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Stream {
+    pub const SEED_PREFIX: &'static [u8] = b"stream";
+
+    /// Amount vested by `now`, capped at the deposit.
+    pub fn vested(&self, now: i64) -> u64 {
+        let elapsed = now.min(self.end_at).saturating_sub(self.start_at).max(0) as u64;
+        elapsed
+            .saturating_mul(self.rate_per_second)
+            .min(self.deposited)
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    /// The vault paying this subscription.
+    pub vault: Pubkey,
+    /// Unique subscription ID (sequential per vault).
+    pub subscription_id: u64,
+    /// The recipient wallet address.
+    pub recipient: Pubkey,
+    /// The recipient's USDC token account.
+    pub recipient_ata: Pubkey,
+    /// Amount (in USDC minor units) paid each interval.
+    pub amount: u64,
+    /// Seconds between payments.
+    pub interval: i64,
+    /// Unix timestamp from which the next payment can be cranked.
+    pub next_payment_at: i64,
+    /// Unix timestamp after which no further payments are due.
+    pub end_at: Option<i64>,
+    /// Maximum number of payments before the subscription ends.
+    pub max_payments: Option<u32>,
+    /// Number of payments made so far.
+    pub payments_made: u32,
+    /// Whether payments are on hold.
+    pub paused: bool,
+    /// Unix timestamp when the subscription was created.
+    pub created_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const SEED_PREFIX: &'static [u8] = b"subscription";
+
+    /// First payment slot at or after `at`, keeping the schedule's phase.
+    /// Slots before `at` are skipped rather than owed.
+    pub fn slot_at_or_after(&self, at: i64) -> Option<i64> {
+        if self.next_payment_at >= at {
+            return Some(self.next_payment_at);
+        }
+        let behind = at.checked_sub(self.next_payment_at)?;
+        let skipped = behind.checked_add(self.interval - 1)? / self.interval;
+        skipped
+            .checked_mul(self.interval)?
+            .checked_add(self.next_payment_at)
+    }
+
+    /// Whether the schedule has run out of payments.
+    pub fn is_ended(&self) -> bool {
+        let past_end = match self.end_at {
+            Some(end_at) => self.next_payment_at > end_at,
+            None => false,
+        };
+        let max_reached = match self.max_payments {
+            Some(max_payments) => self.payments_made >= max_payments,
+            None => false,
+        };
+        past_end || max_reached
+    }
+}
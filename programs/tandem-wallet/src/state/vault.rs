@@ -1,12 +1,26 @@
 use anchor_lang::prelude::*;
+use super::{PendingDelay, SpendWindow};
+
+/// Maximum number of approvers in a vault's approver set.
+pub const MAX_APPROVERS: usize = 8;
+
+/// Maximum number of recovery guardians on a vault.
+pub const MAX_GUARDIANS: usize = 8;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
     /// The human owner who controls this vault.
     pub human: Pubkey,
-    /// The AI agent authorized to propose withdrawals.
+    /// The human key the vault address was derived from. Stays fixed when ownership moves.
+    pub seed_human: Pubkey,
+    /// New human proposed by the current one, waiting to accept ownership.
+    pub pending_human: Option<Pubkey>,
+    /// The primary AI agent authorized to send and propose withdrawals.
+    /// Additional agents are registered through `AgentRole` accounts.
     pub agent: Pubkey,
+    /// The agent key the vault address was derived from. Stays fixed when the agent rotates.
+    pub seed_agent: Pubkey,
     /// The USDC mint address.
     pub usdc_mint: Pubkey,
     /// The vault's associated token account for USDC.
@@ -17,12 +31,126 @@ pub struct Vault {
     pub tier2_max: u64,
     /// Whether the vault is paused (blocks new proposals).
     pub paused: bool,
-    /// Running count of proposals created against this vault.
+    /// Running count of proposals created with `propose`; nonce-keyed ones don't bump it.
     pub proposal_count: u64,
+    /// Rolling 24h budget for agent sends and approved proposals.
+    pub daily_budget: SpendWindow,
+    /// Rolling 7-day budget for agent sends and approved proposals.
+    pub weekly_budget: SpendWindow,
+    /// Seconds before a new or loosened whitelist entry becomes usable by the agent.
+    pub whitelist_delay: i64,
+    /// Lower `whitelist_delay` waiting for the current one to pass.
+    pub pending_whitelist_delay: Option<PendingDelay>,
+    /// Seconds a proposal stays approvable. Zero means proposals never expire.
+    pub proposal_ttl: i64,
+    /// Seconds between final approval and when a proposal can be executed. Zero pays out on approval.
+    pub execution_delay: i64,
+    /// Lower `execution_delay` waiting for the current one to pass.
+    pub pending_execution_delay: Option<PendingDelay>,
+    /// Upper end of the optimistic band above `tier2_max`: USDC proposals up to this amount become
+    /// executable by anyone once `veto_period` passes without a cancel. Zero disables the band.
+    pub optimistic_max: u64,
+    /// Seconds an optimistic proposal waits for a veto before it can be executed.
+    pub veto_period: i64,
+    /// Keys that can approve proposals. Empty means the human approves alone.
+    #[max_len(MAX_APPROVERS)]
+    pub approvers: Vec<Pubkey>,
+    /// Number of approver votes needed to execute a proposal.
+    pub approval_threshold: u8,
+    /// Keys that can jointly appoint a new human if the current one is lost.
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    /// Number of guardian votes needed to start a recovery.
+    pub guardian_threshold: u8,
+    /// Seconds the human has to veto a recovery once guardians reach the threshold.
+    pub recovery_delay: i64,
+    /// USDC (minor units) credited to the vault through `deposit`.
+    pub total_deposited: u64,
+    /// USDC (minor units) debited from the vault as payments and withdrawals, excluding fees.
+    pub total_sent: u64,
+    /// USDC (minor units) debited from the vault as protocol fees.
+    pub total_fees_paid: u64,
+    /// Running count of subscriptions created against this vault.
+    pub subscription_count: u64,
+    /// Running count of streams created against this vault.
+    pub stream_count: u64,
+    /// Running count of escrows created against this vault.
+    pub escrow_count: u64,
+    /// Subscriptions not yet cancelled. The vault can't close while any are open.
+    pub open_subscriptions: u32,
+    /// Streams not yet cancelled. The vault can't close while any are open.
+    pub open_streams: u32,
+    /// Escrows not yet released or refunded. The vault can't close while any are open.
+    pub open_escrows: u32,
+    /// `MintConfig`s on the vault. The vault can't close while any remain.
+    pub mint_configs: u32,
+    /// `AgentRole`s on the vault; closed along with the vault.
+    pub agent_roles: u32,
+    /// `WhitelistEntry`s on the vault; closed along with the vault.
+    pub whitelist_entries: u32,
+    /// Proposals created with `propose` (or migrated) and not yet closed; closed along with
+    /// the vault. Nonce-keyed proposals are counted on their proposer's `AgentLedger` instead.
+    pub open_proposals: u32,
+    /// `AgentLedger`s on the vault; closed along with the vault.
+    pub agent_ledgers: u32,
     /// PDA bump seed.
     pub bump: u8,
 }
 
 impl Vault {
     pub const SEED_PREFIX: &'static [u8] = b"vault";
+
+    /// Whether `key` can vote on proposals.
+    pub fn is_approver(&self, key: &Pubkey) -> bool {
+        if self.approvers.is_empty() {
+            *key == self.human
+        } else {
+            self.approvers.contains(key)
+        }
+    }
+
+    /// Whitelist delay in force at `now`, counting a matured decrease.
+    pub fn whitelist_delay_at(&self, now: i64) -> i64 {
+        PendingDelay::resolve(self.whitelist_delay, self.pending_whitelist_delay, now)
+    }
+
+    /// Execution delay in force at `now`, counting a matured decrease.
+    pub fn execution_delay_at(&self, now: i64) -> i64 {
+        PendingDelay::resolve(self.execution_delay, self.pending_execution_delay, now)
+    }
+
+    /// Whether accounts that pay out of or back into the vault are still open.
+    pub fn has_open_accounts(&self) -> bool {
+        self.open_subscriptions > 0
+            || self.open_streams > 0
+            || self.open_escrows > 0
+            || self.mint_configs > 0
+    }
+
+    /// Number of votes a proposal needs before it executes.
+    pub fn required_approvals(&self) -> usize {
+        if self.approvers.is_empty() {
+            1
+        } else {
+            self.approval_threshold as usize
+        }
+    }
+}
+
+/// Vault layout before budgets, approvers and agent rotation. Only read by `migrate_vault`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyVault {
+    pub human: Pubkey,
+    pub agent: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub vault_usdc_ata: Pubkey,
+    pub tier1_max: u64,
+    pub tier2_max: u64,
+    pub paused: bool,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl LegacyVault {
+    pub const SPACE: usize = 32 * 4 + 8 * 2 + 1 + 8 + 1;
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use super::SpendWindow;
 
 #[account]
 #[derive(InitSpace)]
@@ -9,10 +10,51 @@ pub struct WhitelistEntry {
     pub address: Pubkey,
     /// Unix timestamp when the entry was added.
     pub added_at: i64,
+    /// Unix timestamp from which the agent can use the entry.
+    pub active_from: i64,
+    /// Optional unix timestamp after which the entry no longer applies.
+    pub expires_at: Option<i64>,
+    /// Maximum amount (in USDC minor units) per transfer. Zero means no limit.
+    pub max_per_tx: u64,
+    /// Rolling per-recipient budget. A zero limit means no limit.
+    pub period_budget: SpendWindow,
     /// PDA bump seed.
     pub bump: u8,
 }
 
 impl WhitelistEntry {
     pub const SEED_PREFIX: &'static [u8] = b"whitelist";
+
+    /// Whether the entry is past its activation delay and not yet expired.
+    pub fn is_active(&self, now: i64) -> bool {
+        let unexpired = match self.expires_at {
+            Some(expires_at) => now < expires_at,
+            None => true,
+        };
+        now >= self.active_from && unexpired
+    }
+}
+
+/// Spending limits for a whitelisted recipient. All zeros keeps the entry unlimited.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct WhitelistLimits {
+    /// Maximum amount per transfer. Zero means no limit.
+    pub max_per_tx: u64,
+    /// Maximum amount per period. Zero means no limit.
+    pub max_per_period: u64,
+    /// Length of the period in seconds. Required when `max_per_period` is set.
+    pub period_seconds: i64,
+}
+
+/// Whitelist entry layout before limits and activation delays. Only read by `migrate_vault`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyWhitelistEntry {
+    pub vault: Pubkey,
+    pub address: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyWhitelistEntry {
+    pub const SPACE: usize = 32 * 2 + 8 + 1;
 }
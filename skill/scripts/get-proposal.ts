@@ -1,5 +1,5 @@
 import { getProgram, getVaultAddress, getProgramId } from "./lib/client";
-import { formatUsdc } from "./lib/format";
+import { formatEnum, formatProposalNumber, formatUsdc } from "./lib/format";
 import { PublicKey } from "@solana/web3.js";
 import BN from "bn.js";
 
@@ -13,24 +13,38 @@ async function main() {
   const vaultAddress = getVaultAddress();
   const programId = getProgramId();
 
-  const id = new BN(parseInt(proposalId));
+  const id = new BN(proposalId);
   const [proposalPda] = PublicKey.findProgramAddressSync(
     [Buffer.from("proposal"), vaultAddress.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
     programId
   );
 
   const proposal = await (program.account as any).proposal.fetch(proposalPda);
-  const status = proposal.executed ? "executed" : proposal.cancelled ? "cancelled" : "pending";
+  const status = proposal.executed
+    ? "executed"
+    : proposal.cancelled
+      ? "cancelled"
+      : proposal.executableAt
+        ? "scheduled"
+        : "pending";
 
   console.log(JSON.stringify({
-    id: parseInt(proposalId),
+    number: formatProposalNumber(proposal),
+    id: proposalId,
     vault: proposal.vault.toBase58(),
     recipient: proposal.recipient.toBase58(),
     recipientAta: proposal.recipientAta.toBase58(),
     amount: formatUsdc(proposal.amount),
+    approvedAmount: proposal.approvedAmount ? formatUsdc(proposal.approvedAmount) : null,
     status,
     memo: proposal.memo,
+    revision: proposal.revision,
+    cancelReason: formatEnum(proposal.cancelReason),
+    cancelNote: proposal.cancelled ? proposal.cancelNote : null,
     proposedAt: new Date(Number(proposal.proposedAt) * 1000).toISOString(),
+    executableAt: proposal.executableAt
+      ? new Date(Number(proposal.executableAt) * 1000).toISOString()
+      : null,
   }, null, 2));
 }
 
@@ -16,6 +16,18 @@ export function formatSol(lamports: number): string {
   return `${(lamports / 1e9).toFixed(4)} SOL`;
 }
 
+// Anchor decodes enums as `{ variantName: {} }`; return the variant name.
+export function formatEnum(value: object | null): string | null {
+  return value ? Object.keys(value)[0] : null;
+}
+
 export function formatToken(raw: bigint | number, decimals: number, symbol: string): string {
   return `${(Number(raw) / 10 ** decimals).toFixed(decimals > 4 ? 4 : 2)} ${symbol}`;
 }
+
+// Stable display number for a proposal: its ID when sequential, or the first
+// characters of the proposer plus their running count when nonce-keyed.
+export function formatProposalNumber(proposal: any): string {
+  if (!proposal.proposalId.testn(63)) return proposal.sequence.toString();
+  return `${proposal.proposer.toBase58().slice(0, 4)}-${proposal.sequence.toString()}`;
+}
@@ -1,42 +1,38 @@
-import { getProgram, getVaultAddress, getProgramId } from "./lib/client";
-import { formatUsdc } from "./lib/format";
-import { PublicKey } from "@solana/web3.js";
-import BN from "bn.js";
+import { getProgram, getVaultAddress } from "./lib/client";
+import { formatEnum, formatProposalNumber, formatUsdc } from "./lib/format";
 
 async function main() {
   const filter = process.argv[2] || "pending";
   const program = getProgram();
   const vaultAddress = getVaultAddress();
-  const programId = getProgramId();
 
-  const vault = await (program.account as any).vault.fetch(vaultAddress);
-  const proposalCount = Number(vault.proposalCount);
+  // Proposals may be keyed by the vault's counter or by an agent nonce, so look
+  // them up by vault (offset 8, after the discriminator) rather than by ID.
+  // Closed proposals (rent reclaimed) no longer show up.
+  const accounts = await (program.account as any).proposal.all([
+    { memcmp: { offset: 8, bytes: vaultAddress.toBase58() } },
+  ]);
+  accounts.sort((a: any, b: any) => Number(a.account.proposedAt) - Number(b.account.proposedAt));
 
   const proposals: any[] = [];
-  for (let i = 0; i < proposalCount; i++) {
-    const id = new BN(i);
-    const [proposalPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("proposal"), vaultAddress.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
-      programId
-    );
-
-    try {
-      const proposal = await (program.account as any).proposal.fetch(proposalPda);
-      const status = proposal.executed ? "executed" : proposal.cancelled ? "cancelled" : "pending";
-
-      if (filter !== "all" && status !== filter) continue;
-
-      proposals.push({
-        id: i,
-        recipient: proposal.recipient.toBase58(),
-        amount: formatUsdc(proposal.amount),
-        status,
-        memo: proposal.memo,
-        proposedAt: new Date(Number(proposal.proposedAt) * 1000).toISOString(),
-      });
-    } catch {
-      // Proposal account closed (rent reclaimed)
-    }
+  for (const { account: proposal } of accounts) {
+    const status = proposal.executed ? "executed" : proposal.cancelled ? "cancelled" : "pending";
+
+    if (filter !== "all" && status !== filter) continue;
+
+    proposals.push({
+      number: formatProposalNumber(proposal),
+      id: proposal.proposalId.toString(),
+      recipient: proposal.recipient.toBase58(),
+      amount: formatUsdc(proposal.amount),
+      status,
+      memo: proposal.memo,
+      ...(proposal.cancelled && {
+        cancelReason: formatEnum(proposal.cancelReason),
+        cancelNote: proposal.cancelNote,
+      }),
+      proposedAt: new Date(Number(proposal.proposedAt) * 1000).toISOString(),
+    });
   }
 
   console.log(JSON.stringify({ filter, proposals }, null, 2));
@@ -1,22 +1,31 @@
-import { getProgram, getVaultAddress, getConnection, getAgentKeypair, getProgramId, getProtocolConfigAddress } from "./lib/client";
+import { getProgram, getVaultAddress, getAgentKeypair, getProgramId, getProtocolConfigAddress } from "./lib/client";
 import { usdcToRaw, formatUsdc } from "./lib/format";
-import { getOrCreateAssociatedTokenAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { PublicKey } from "@solana/web3.js";
 import BN from "bn.js";
+import { randomBytes } from "crypto";
+
+const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+function flagValue(args: string[], flag: string): string | null {
+  const i = args.indexOf(flag);
+  return i >= 0 && i + 1 < args.length ? args[i + 1] : null;
+}
 
 async function main() {
   const args = process.argv.slice(2);
-  if (args.length < 2) {
-    console.error(JSON.stringify({ error: "Usage: send-usdc.ts <recipient> <amount> [--emergency]" }));
+  const memo = flagValue(args, "--memo");
+  if (args.length < 2 || !memo) {
+    console.error(JSON.stringify({ error: "Usage: send-usdc.ts <recipient> <amount> --memo <text> [--emergency] [--invoice <id>]" }));
     process.exit(1);
   }
 
   const recipientAddress = new PublicKey(args[0]);
   const amount = parseFloat(args[1]);
   const isEmergency = args.includes("--emergency");
+  const invoiceId = flagValue(args, "--invoice");
   const rawAmount = usdcToRaw(amount);
   const program = getProgram();
-  const connection = getConnection();
   const vaultAddress = getVaultAddress();
   const agentKeypair = getAgentKeypair();
   const programId = getProgramId();
@@ -27,10 +36,8 @@ async function main() {
   const protocolConfigAddress = getProtocolConfigAddress();
   const protocolConfig = await (program.account as any).protocolConfig.fetch(protocolConfigAddress);
 
-  // Get or create recipient ATA
-  const recipientAta = await getOrCreateAssociatedTokenAccount(
-    connection, agentKeypair, new PublicKey(vault.usdcMint), recipientAddress
-  );
+  // Recipient ATA; the program creates it if it doesn't exist yet
+  const recipientAta = getAssociatedTokenAddressSync(new PublicKey(vault.usdcMint), recipientAddress);
 
   // Check whitelist
   const [whitelistPda] = PublicKey.findProgramAddressSync(
@@ -74,22 +81,47 @@ async function main() {
       amount: formatUsdc(rawAmount),
     }, null, 2));
 
-    const proposalId = vault.proposalCount;
+    // Random nonce with the top bit set, so parallel proposals don't race for an ID
+    const nonceBytes = randomBytes(8);
+    nonceBytes[7] |= 0x80;
+    const proposalId = new BN(nonceBytes, "le");
     const [proposalPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("proposal"), vaultAddress.toBuffer(), proposalId.toArrayLike(Buffer, "le", 8)],
+      [Buffer.from("proposal"), vaultAddress.toBuffer(), nonceBytes],
       programId
     );
 
+    // Nonce proposals are counted on the agent's ledger; open it on first use
+    const [agentLedger] = PublicKey.findProgramAddressSync(
+      [Buffer.from("agent_ledger"), vaultAddress.toBuffer(), agentKeypair.publicKey.toBuffer()],
+      programId
+    );
+    const preInstructions = [];
+    if (!(await program.provider.connection.getAccountInfo(agentLedger))) {
+      preInstructions.push(
+        await (program.methods as any)
+          .openAgentLedger()
+          .accounts({
+            agent: agentKeypair.publicKey,
+            vault: vaultAddress,
+            agentLedger,
+            systemProgram: PublicKey.default,
+          })
+          .instruction()
+      );
+    }
+
     const tx = await (program.methods as any)
-      .propose(new BN(rawAmount.toString()), `Send ${amount} USDC to ${recipientAddress.toBase58().slice(0, 8)}...`)
+      .proposeWithNonce(proposalId, new BN(rawAmount.toString()), memo)
       .accounts({
         agent: agentKeypair.publicKey,
         vault: vaultAddress,
         recipient: recipientAddress,
-        recipientAta: recipientAta.address,
+        recipientAta,
         proposal: proposalPda,
+        agentLedger,
         systemProgram: PublicKey.default,
       })
+      .preInstructions(preInstructions)
       .signers([agentKeypair])
       .rpc();
 
@@ -105,17 +137,19 @@ async function main() {
 
   // Execute send
   const tx = await (program.methods as any)
-    .sendUsdc(new BN(rawAmount.toString()), isEmergency)
+    .sendUsdc(new BN(rawAmount.toString()), isEmergency, memo, invoiceId)
     .accounts({
       signer: agentKeypair.publicKey,
       vault: vaultAddress,
       vaultUsdcAta: new PublicKey(vault.vaultUsdcAta),
-      recipientAta: recipientAta.address,
+      recipientAta,
+      recipient: recipientAddress,
       whitelistEntry: whitelistEntry,
       protocolConfig: protocolConfigAddress,
       stakerRewardAta: new PublicKey(protocolConfig.stakerRewardAta),
       buybackAta: new PublicKey(protocolConfig.buybackAta),
       tokenProgram: TOKEN_PROGRAM_ID,
+      memoProgram: MEMO_PROGRAM_ID,
     })
     .signers([agentKeypair])
     .rpc();
@@ -126,6 +160,8 @@ async function main() {
     amount: formatUsdc(rawAmount),
     whitelisted: !!whitelistEntry,
     isEmergency,
+    memo,
+    invoiceId,
     tx,
   }, null, 2));
 }
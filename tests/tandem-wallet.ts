@@ -6,8 +6,13 @@ import {
   getAccount,
   getOrCreateAssociatedTokenAccount,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
+  ExtensionType,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
 } from "@solana/spl-token";
 import { expect } from "chai";
 import {
@@ -15,6 +20,8 @@ import {
   Keypair,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
+  Transaction,
+  sendAndConfirmTransaction,
 } from "@solana/web3.js";
 import BN from "bn.js";
 import * as fs from "fs";
@@ -24,6 +31,8 @@ import { fileURLToPath } from "url";
 // Load IDL directly since generated types may not match
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
+const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
 const idl = JSON.parse(fs.readFileSync(path.join(__dirname, "../target/idl/tandem_wallet.json"), "utf-8"));
 
 describe("tandem-wallet", () => {
@@ -43,6 +52,7 @@ describe("tandem-wallet", () => {
   let vaultBump: number;
   let vaultUsdcAta: PublicKey;
   let recipientAta: PublicKey;
+  let humanUsdcAta: PublicKey;
 
   // Protocol accounts
   let protocolConfig: PublicKey;
@@ -55,6 +65,7 @@ describe("tandem-wallet", () => {
   const TIER2_MAX = new BN(100_000_000); // 100 USDC
   const INITIAL_VAULT_BALANCE = 1_000_000_000; // 1000 USDC
   const FEE_BPS = 25; // 0.25%
+  const UNLIMITED_WHITELIST = { maxPerTx: new BN(0), maxPerPeriod: new BN(0), periodSeconds: new BN(0) };
 
   before("Setup test environment", async () => {
     mintAuthority = Keypair.generate();
@@ -158,6 +169,7 @@ describe("tandem-wallet", () => {
         stakeTandemAta,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
+        tandemTokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -173,11 +185,76 @@ describe("tandem-wallet", () => {
     expect(config.totalStaked.toNumber()).to.equal(0);
   });
 
-  it("Funds the vault with USDC", async () => {
-    await mintTo(provider.connection, mintAuthority, usdcMint, vaultUsdcAta, mintAuthority, INITIAL_VAULT_BALANCE);
+  it("Funds the vault with USDC through deposit", async () => {
+    humanUsdcAta = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, mintAuthority, usdcMint, human
+    )).address;
+    await mintTo(provider.connection, mintAuthority, usdcMint, humanUsdcAta, mintAuthority, INITIAL_VAULT_BALANCE);
+
+    await program.methods
+      .deposit(new BN(INITIAL_VAULT_BALANCE), "Initial funding")
+      .accounts({
+        depositor: human,
+        vault,
+        usdcMint,
+        depositorAta: humanUsdcAta,
+        vaultUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
 
     const balance = await getAccount(provider.connection, vaultUsdcAta);
     expect(Number(balance.amount)).to.equal(INITIAL_VAULT_BALANCE);
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    expect(vaultAccount.totalDeposited.toNumber()).to.equal(INITIAL_VAULT_BALANCE);
+  });
+
+  // Balance implied by the vault's counters must match the real ATA balance
+  async function expectReconciled() {
+    await program.methods.reconcile().accounts({ vault, vaultUsdcAta }).rpc();
+    const v = await program.account.vault.fetch(vault);
+    const balance = await getAccount(provider.connection, vaultUsdcAta);
+    const expected = v.totalDeposited.sub(v.totalSent).sub(v.totalFeesPaid);
+    expect(expected.toString()).to.equal(balance.amount.toString());
+  }
+
+  it("Anyone can reconcile a freshly funded vault", async () => {
+    await expectReconciled();
+  });
+
+  it("Human withdraws to their own account without a fee", async () => {
+    const beforeHuman = await getAccount(provider.connection, humanUsdcAta);
+    const beforeBuyback = await getAccount(provider.connection, buybackAta);
+
+    await program.methods
+      .withdraw(new BN(10_000_000))
+      .accounts({ human, vault, usdcMint, vaultUsdcAta, humanUsdcAta, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    const afterHuman = await getAccount(provider.connection, humanUsdcAta);
+    const afterBuyback = await getAccount(provider.connection, buybackAta);
+    expect(Number(afterHuman.amount) - Number(beforeHuman.amount)).to.equal(10_000_000);
+    expect(Number(afterBuyback.amount)).to.equal(Number(beforeBuyback.amount));
+
+    // Put it back so later balance checks are unaffected
+    await program.methods
+      .deposit(new BN(10_000_000), null)
+      .accounts({ depositor: human, vault, usdcMint, depositorAta: humanUsdcAta, vaultUsdcAta, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+    await expectReconciled();
+  });
+
+  it("Withdraw to someone else's account fails (InvalidTokenAccount)", async () => {
+    try {
+      await program.methods
+        .withdraw(new BN(1_000_000))
+        .accounts({ human, vault, usdcMint, vaultUsdcAta, humanUsdcAta: recipientAta, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidTokenAccount");
+    }
   });
 
   // --- Tier routing tests (now with fee accounts) ---
@@ -189,15 +266,19 @@ describe("tandem-wallet", () => {
     const beforeBuyback = await getAccount(provider.connection, buybackAta);
 
     await program.methods
-      .sendUsdc(amount, false)
+      .sendUsdc(amount, false, "Test payment", null)
       .accounts({
         signer: agent.publicKey,
         vault,
+        usdcMint,
         vaultUsdcAta,
         recipientAta,
+        recipient: null,
         whitelistEntry: null,
+        agentRole: null,
         ...feeAccounts(),
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
       })
       .signers([agent])
       .rpc();
@@ -220,15 +301,19 @@ describe("tandem-wallet", () => {
   it("Tier 2 without emergency flag fails (NotEmergency)", async () => {
     try {
       await program.methods
-        .sendUsdc(new BN(75_000_000), false)
+        .sendUsdc(new BN(75_000_000), false, "Test payment", null)
         .accounts({
           signer: agent.publicKey,
           vault,
+          usdcMint,
           vaultUsdcAta,
           recipientAta,
+          recipient: null,
           whitelistEntry: null,
+          agentRole: null,
           ...feeAccounts(),
           tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
         })
         .signers([agent])
         .rpc();
@@ -242,15 +327,19 @@ describe("tandem-wallet", () => {
     const before = await getAccount(provider.connection, recipientAta);
 
     await program.methods
-      .sendUsdc(new BN(75_000_000), true)
+      .sendUsdc(new BN(75_000_000), true, "Test payment", null)
       .accounts({
         signer: agent.publicKey,
         vault,
+        usdcMint,
         vaultUsdcAta,
         recipientAta,
+        recipient: null,
         whitelistEntry: null,
+        agentRole: null,
         ...feeAccounts(),
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
       })
       .signers([agent])
       .rpc();
@@ -262,15 +351,19 @@ describe("tandem-wallet", () => {
   it("Over tier2_max fails (TierTooHigh)", async () => {
     try {
       await program.methods
-        .sendUsdc(new BN(150_000_000), true)
+        .sendUsdc(new BN(150_000_000), true, "Test payment", null)
         .accounts({
           signer: agent.publicKey,
           vault,
+          usdcMint,
           vaultUsdcAta,
           recipientAta,
+          recipient: null,
           whitelistEntry: null,
+          agentRole: null,
           ...feeAccounts(),
           tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
         })
         .signers([agent])
         .rpc();
@@ -280,6 +373,108 @@ describe("tandem-wallet", () => {
     }
   });
 
+  it("Send carries a memo and invoice ID into the transaction log", async () => {
+    const sig = await program.methods
+      .sendUsdc(new BN(1_000_000), false, "API credits for October", "INV-0042")
+      .accounts({
+        signer: agent.publicKey,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: null,
+        agentRole: null,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc({ commitment: "confirmed" });
+
+    const tx = await provider.connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const logs = tx.meta.logMessages.join("\n");
+    expect(logs).to.include("API credits for October | invoice:INV-0042");
+  });
+
+  it("Memo over 128 bytes fails (MemoTooLong)", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(1_000_000), false, "x".repeat(129), null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("MemoTooLong");
+    }
+  });
+
+  it("Invoice ID over 64 bytes fails (InvoiceIdTooLong)", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(1_000_000), false, "API credits", "x".repeat(65))
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvoiceIdTooLong");
+    }
+  });
+
+  it("Send without a memo fails (MemoRequired)", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(1_000_000), false, "", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("MemoRequired");
+    }
+  });
+
   // --- Proposal tests ---
 
   let proposal1Pda: PublicKey;
@@ -299,6 +494,8 @@ describe("tandem-wallet", () => {
         recipient: recipient.publicKey,
         recipientAta,
         proposal: proposal1Pda,
+        agentRole: null,
+        mintConfig: null,
         systemProgram: SystemProgram.programId,
       })
       .signers([agent])
@@ -320,14 +517,18 @@ describe("tandem-wallet", () => {
     const beforeBuyback = await getAccount(provider.connection, buybackAta);
 
     await program.methods
-      .approveProposal()
+      .approveProposal(0, new BN(150_000_000), null)
       .accounts({
-        human,
+        approver: human,
         vault,
         proposal: proposal1Pda,
-        vaultUsdcAta,
+        mint: usdcMint,
+        mintConfig: null,
+        vaultTokenAta: vaultUsdcAta,
         recipientAta,
+        recipient: null,
         ...feeAccounts(),
+        tokenBuybackAta: null,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
@@ -345,6 +546,12 @@ describe("tandem-wallet", () => {
 
     const proposal = await program.account.proposal.fetch(proposal1Pda);
     expect(proposal.executed).to.be.true;
+
+    // Withdrawals, sends and approvals are tracked: 10 + 30 + 75 + 1 + 150 sent, fees on all but the withdrawal
+    const v = await program.account.vault.fetch(vault);
+    expect(v.totalSent.toNumber()).to.equal(266_000_000);
+    expect(v.totalFeesPaid.toNumber()).to.equal(75_000 + 187_500 + 2_500 + 375_000);
+    await expectReconciled();
   });
 
   let proposal2Pda: PublicKey;
@@ -364,24 +571,28 @@ describe("tandem-wallet", () => {
         recipient: recipient.publicKey,
         recipientAta,
         proposal: proposal2Pda,
+        agentRole: null,
+        mintConfig: null,
         systemProgram: SystemProgram.programId,
       })
       .signers([agent])
       .rpc();
 
     await program.methods
-      .cancelProposal()
-      .accounts({ human, vault, proposal: proposal2Pda })
+      .cancelProposal({ amountTooHigh: {} }, "Split this into two smaller payments")
+      .accounts({ signer: human, vault, proposal: proposal2Pda })
       .rpc();
 
     const proposal = await program.account.proposal.fetch(proposal2Pda);
     expect(proposal.cancelled).to.be.true;
+    expect(proposal.cancelReason).to.deep.equal({ amountTooHigh: {} });
+    expect(proposal.cancelNote).to.equal("Split this into two smaller payments");
   });
 
   it("Agent closes executed proposal (rent reclaimed)", async () => {
     await program.methods
       .closeProposal()
-      .accounts({ agent: agent.publicKey, vault, proposal: proposal1Pda })
+      .accounts({ agent: agent.publicKey, vault, agentRole: null, proposal: proposal1Pda, proposer: agent.publicKey, agentLedger: null })
       .signers([agent])
       .rpc();
 
@@ -393,194 +604,2449 @@ describe("tandem-wallet", () => {
     }
   });
 
-  // --- Whitelist tests ---
-
-  let whitelistPda: PublicKey;
+  it("Expired proposal can't be approved and anyone can close it", async () => {
+    await program.methods
+      .setProposalTtl(new BN(1))
+      .accounts({ human, vault })
+      .rpc();
 
-  it("Human adds recipient to whitelist", async () => {
-    [whitelistPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("whitelist"), vault.toBuffer(), recipient.publicKey.toBuffer()],
+    const v = await program.account.vault.fetch(vault);
+    const [expiringPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
     await program.methods
-      .addWhitelist(recipient.publicKey)
+      .propose(new BN(150_000_000), "Goes stale")
       .accounts({
-        human,
+        agent: agent.publicKey,
         vault,
-        whitelistEntry: whitelistPda,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: expiringPda,
+        agentRole: null,
+        mintConfig: null,
         systemProgram: SystemProgram.programId,
       })
+      .signers([agent])
       .rpc();
 
-    const wl = await program.account.whitelistEntry.fetch(whitelistPda);
-    expect(wl.address.toString()).to.equal(recipient.publicKey.toString());
+    const created = await program.account.proposal.fetch(expiringPda);
+    expect(created.proposer.toString()).to.equal(agent.publicKey.toString());
+    expect(created.expiresAt.toNumber()).to.equal(created.proposedAt.toNumber() + 1);
+
+    await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+    try {
+      await program.methods
+        .approveProposal(0, new BN(150_000_000), null)
+        .accounts({
+          approver: human,
+          vault,
+          proposal: expiringPda,
+          mint: usdcMint,
+          mintConfig: null,
+          vaultTokenAta: vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          ...feeAccounts(),
+          tokenBuybackAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ProposalExpired");
+    }
+
+    const agentBefore = await provider.connection.getBalance(agent.publicKey);
+    await program.methods
+      .closeExpiredProposal()
+      .accounts({ closer: recipient.publicKey, vault, proposal: expiringPda, proposer: agent.publicKey, agentLedger: null })
+      .signers([recipient])
+      .rpc();
+    const agentAfter = await provider.connection.getBalance(agent.publicKey);
+    expect(agentAfter).to.be.greaterThan(agentBefore);
+
+    await program.methods
+      .setProposalTtl(new BN(0))
+      .accounts({ human, vault })
+      .rpc();
   });
 
-  it("Agent sends over tier2_max to whitelisted recipient (200 USDC)", async () => {
-    const before = await getAccount(provider.connection, recipientAta);
+  it("2-of-2 approvers: transfer runs once the threshold is met, for the smallest vote", async () => {
+    const secondApprover = Keypair.generate();
+    await program.methods.addApprover(human).accounts({ human, vault }).rpc();
+    await program.methods.addApprover(secondApprover.publicKey).accounts({ human, vault }).rpc();
+    await program.methods.setApprovalThreshold(2).accounts({ human, vault }).rpc();
+
+    const v = await program.account.vault.fetch(vault);
+    expect(v.approvers.length).to.equal(2);
+    expect(v.approvalThreshold).to.equal(2);
 
+    const [multisigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
     await program.methods
-      .sendUsdc(new BN(200_000_000), false)
+      .propose(new BN(120_000_000), "Needs two signatures")
       .accounts({
-        signer: agent.publicKey,
+        agent: agent.publicKey,
         vault,
-        vaultUsdcAta,
+        recipient: recipient.publicKey,
         recipientAta,
-        whitelistEntry: whitelistPda,
-        ...feeAccounts(),
-        tokenProgram: TOKEN_PROGRAM_ID,
+        proposal: multisigPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
       })
       .signers([agent])
       .rpc();
 
-    const after = await getAccount(provider.connection, recipientAta);
-    expect(Number(after.amount) - Number(before.amount)).to.equal(200_000_000);
-  });
+    const approveAccounts = {
+      vault,
+      proposal: multisigPda,
+      mint: usdcMint,
+      mintConfig: null,
+      vaultTokenAta: vaultUsdcAta,
+      recipientAta,
+      recipient: null,
+      ...feeAccounts(),
+      tokenBuybackAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const before = await getAccount(provider.connection, recipientAta);
 
-  it("Human removes recipient from whitelist", async () => {
     await program.methods
-      .removeWhitelist()
-      .accounts({ human, vault, whitelistEntry: whitelistPda })
+      .approveProposal(0, new BN(120_000_000), new BN(100_000_000))
+      .accounts({ approver: human, ...approveAccounts })
       .rpc();
 
-    try {
-      await program.account.whitelistEntry.fetch(whitelistPda);
-      expect.fail("Should be closed");
-    } catch (e: any) {
-      expect(e.message).to.include("Account does not exist");
-    }
-  });
+    let proposal = await program.account.proposal.fetch(multisigPda);
+    expect(proposal.approvals.length).to.equal(1);
+    expect(proposal.approvalAmounts[0].toNumber()).to.equal(100_000_000);
+    expect(proposal.executed).to.be.false;
 
-  it("Agent over-tier1 send fails after whitelist removal", async () => {
     try {
       await program.methods
-        .sendUsdc(new BN(75_000_000), false)
-        .accounts({
-          signer: agent.publicKey,
-          vault,
-          vaultUsdcAta,
-          recipientAta,
-          whitelistEntry: null,
-          ...feeAccounts(),
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([agent])
+        .approveProposal(0, new BN(120_000_000), null)
+        .accounts({ approver: human, ...approveAccounts })
         .rpc();
       expect.fail("Should have thrown");
     } catch (e: any) {
-      expect(e.error.errorCode.code).to.equal("NotEmergency");
+      expect(e.error.errorCode.code).to.equal("AlreadyApproved");
     }
-  });
-
-  // --- Admin tests ---
-
-  it("Human updates tiers", async () => {
-    const newT1 = new BN(75_000_000);
-    const newT2 = new BN(150_000_000);
 
     await program.methods
-      .setTiers(newT1, newT2)
-      .accounts({ human, vault })
+      .approveProposal(0, new BN(120_000_000), null)
+      .accounts({ approver: secondApprover.publicKey, ...approveAccounts })
+      .signers([secondApprover])
       .rpc();
 
-    const v = await program.account.vault.fetch(vault);
-    expect(v.tier1Max.toString()).to.equal(newT1.toString());
-    expect(v.tier2Max.toString()).to.equal(newT2.toString());
+    // The second approver voted for the full amount; the first one's lower vote wins
+    proposal = await program.account.proposal.fetch(multisigPda);
+    expect(proposal.executed).to.be.true;
+    expect(proposal.approvedAmount.toNumber()).to.equal(100_000_000);
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(100_000_000);
+
+    // Back to human-only approval
+    await program.methods.setApprovalThreshold(1).accounts({ human, vault }).rpc();
+    await program.methods.removeApprover(secondApprover.publicKey).accounts({ human, vault }).rpc();
+    await program.methods.removeApprover(human).accounts({ human, vault }).rpc();
+    const reset = await program.account.vault.fetch(vault);
+    expect(reset.approvers.length).to.equal(0);
+    expect(reset.approvalThreshold).to.equal(0);
   });
 
-  it("Human pauses vault", async () => {
-    await program.methods.pause().accounts({ human, vault }).rpc();
+  it("Propose with a token account the recipient doesn't own fails (InvalidTokenAccount)", async () => {
     const v = await program.account.vault.fetch(vault);
-    expect(v.paused).to.be.true;
-  });
-
-  it("Agent send fails when paused", async () => {
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
     try {
       await program.methods
-        .sendUsdc(new BN(10_000_000), false)
+        .propose(new BN(150_000_000), "Wrong account")
         .accounts({
-          signer: agent.publicKey,
+          agent: agent.publicKey,
           vault,
-          vaultUsdcAta,
-          recipientAta,
-          whitelistEntry: null,
-          ...feeAccounts(),
-          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipient.publicKey,
+          recipientAta: humanUsdcAta,
+          proposal: proposalPda,
+          agentRole: null,
+          mintConfig: null,
+          systemProgram: SystemProgram.programId,
         })
         .signers([agent])
         .rpc();
       expect.fail("Should have thrown");
     } catch (e: any) {
-      expect(e.error.errorCode.code).to.equal("VaultPaused");
+      expect(e.error.errorCode.code).to.equal("InvalidTokenAccount");
     }
   });
 
-  it("Human can still send when paused", async () => {
-    const before = await getAccount(provider.connection, recipientAta);
+  it("Proposal to a wallet without a token account; approval creates it", async () => {
+    const newRecipient = Keypair.generate();
+    const newRecipientAta = getAssociatedTokenAddressSync(usdcMint, newRecipient.publicKey);
+    const v = await program.account.vault.fetch(vault);
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
 
     await program.methods
-      .sendUsdc(new BN(10_000_000), false)
+      .propose(new BN(20_000_000), "First payment to a new vendor")
       .accounts({
-        signer: human,
+        agent: agent.publicKey,
         vault,
-        vaultUsdcAta,
-        recipientAta,
-        whitelistEntry: null,
+        recipient: newRecipient.publicKey,
+        recipientAta: newRecipientAta,
+        proposal: proposalPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(newRecipientAta)).to.be.null;
+
+    await program.methods
+      .approveProposal(0, new BN(20_000_000), null)
+      .accounts({
+        approver: human,
+        vault,
+        proposal: proposalPda,
+        mint: usdcMint,
+        mintConfig: null,
+        vaultTokenAta: vaultUsdcAta,
+        recipientAta: newRecipientAta,
+        recipient: newRecipient.publicKey,
         ...feeAccounts(),
+        tokenBuybackAta: null,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
 
-    const after = await getAccount(provider.connection, recipientAta);
-    expect(Number(after.amount) - Number(before.amount)).to.equal(10_000_000);
-  });
-
-  it("Human unpauses vault", async () => {
-    await program.methods.unpause().accounts({ human, vault }).rpc();
-    const v = await program.account.vault.fetch(vault);
-    expect(v.paused).to.be.false;
+    const created = await getAccount(provider.connection, newRecipientAta);
+    expect(Number(created.amount)).to.equal(20_000_000);
+    await expectReconciled();
   });
 
-  it("Agent send succeeds after unpause", async () => {
-    const before = await getAccount(provider.connection, recipientAta);
+  it("Agent sends to a wallet without a token account, creating it first", async () => {
+    const newRecipient = Keypair.generate();
+    const newRecipientAta = getAssociatedTokenAddressSync(usdcMint, newRecipient.publicKey);
 
     await program.methods
-      .sendUsdc(new BN(10_000_000), false)
+      .sendUsdc(new BN(2_000_000), false, "Test payment", null)
       .accounts({
         signer: agent.publicKey,
         vault,
+        usdcMint,
         vaultUsdcAta,
-        recipientAta,
+        recipientAta: newRecipientAta,
+        recipient: newRecipient.publicKey,
         whitelistEntry: null,
+        agentRole: null,
         ...feeAccounts(),
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
       })
       .signers([agent])
       .rpc();
 
-    const after = await getAccount(provider.connection, recipientAta);
-    expect(Number(after.amount) - Number(before.amount)).to.equal(10_000_000);
+    const created = await getAccount(provider.connection, newRecipientAta);
+    expect(Number(created.amount)).to.equal(2_000_000);
   });
 
-  // --- Fee precision tests ---
-
-  it("Tiny amount: fee rounds to 0, no fee transfers", async () => {
-    // 100 lamports = 0.0001 USDC. fee = 100 * 25 / 10000 = 0
+  it("Agent amends its proposal; approving the old revision fails", async () => {
+    const v = await program.account.vault.fetch(vault);
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .propose(new BN(150_000_000), "Typo in amount")
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: proposalPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    await program.methods
+      .amendProposal(new BN(15_000_000), "Corrected amount")
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        agentRole: null,
+        proposal: proposalPda,
+        recipient: null,
+        recipientAta: null,
+      })
+      .signers([agent])
+      .rpc();
+
+    const amended = await program.account.proposal.fetch(proposalPda);
+    expect(amended.amount.toNumber()).to.equal(15_000_000);
+    expect(amended.memo).to.equal("Corrected amount");
+    expect(amended.revision).to.equal(1);
+
+    const approveAccounts = {
+      approver: human,
+      vault,
+      proposal: proposalPda,
+      mint: usdcMint,
+      mintConfig: null,
+      vaultTokenAta: vaultUsdcAta,
+      recipientAta,
+      recipient: null,
+      ...feeAccounts(),
+      tokenBuybackAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    try {
+      await program.methods.approveProposal(0, new BN(15_000_000), null).accounts(approveAccounts).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ProposalRevisionMismatch");
+    }
+
+    const before = await getAccount(provider.connection, recipientAta);
+    await program.methods.approveProposal(1, new BN(15_000_000), null).accounts(approveAccounts).rpc();
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(15_000_000);
+  });
+
+  it("Agent withdraws its pending proposal and gets the rent back", async () => {
+    const v = await program.account.vault.fetch(vault);
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .propose(new BN(150_000_000), "Not needed after all")
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: proposalPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    const rent = await provider.connection.getBalance(proposalPda);
+    const before = await provider.connection.getBalance(agent.publicKey);
+
+    await program.methods
+      .withdrawProposal()
+      .accounts({ agent: agent.publicKey, vault, proposal: proposalPda, agentLedger: null })
+      .signers([agent])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(proposalPda)).to.be.null;
+    const after = await provider.connection.getBalance(agent.publicKey);
+    expect(after - before).to.equal(rent - 5000);
+  });
+
+  it("Human approves less than requested; both amounts are recorded", async () => {
+    const v = await program.account.vault.fetch(vault);
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .propose(new BN(30_000_000), "Asks for 30")
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: proposalPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    const approveAccounts = {
+      approver: human,
+      vault,
+      proposal: proposalPda,
+      mint: usdcMint,
+      mintConfig: null,
+      vaultTokenAta: vaultUsdcAta,
+      recipientAta,
+      recipient: null,
+      ...feeAccounts(),
+      tokenBuybackAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    try {
+      await program.methods.approveProposal(0, new BN(30_000_000), new BN(31_000_000)).accounts(approveAccounts).rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidApprovedAmount");
+    }
+
+    const before = await getAccount(provider.connection, recipientAta);
+    await program.methods.approveProposal(0, new BN(30_000_000), new BN(20_000_000)).accounts(approveAccounts).rpc();
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(20_000_000);
+
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    expect(proposal.amount.toNumber()).to.equal(30_000_000);
+    expect(proposal.approvedAmount.toNumber()).to.equal(20_000_000);
+    await expectReconciled();
+  });
+
+  // --- Execution delay tests ---
+
+  async function proposeForDelay(memo: string) {
+    const v = await program.account.vault.fetch(vault);
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .propose(new BN(10_000_000), memo)
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: proposalPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return proposalPda;
+  }
+
+  function payoutAccounts(proposalPda: PublicKey) {
+    return {
+      vault,
+      proposal: proposalPda,
+      mint: usdcMint,
+      mintConfig: null,
+      vaultTokenAta: vaultUsdcAta,
+      recipientAta,
+      recipient: null,
+      ...feeAccounts(),
+      tokenBuybackAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  it("With an execution delay, approval schedules and anyone executes afterwards", async () => {
+    await program.methods.setExecutionDelay(new BN(2)).accounts({ human, vault }).rpc();
+    const proposalPda = await proposeForDelay("Delayed payout");
+    const before = await getAccount(provider.connection, recipientAta);
+
+    await program.methods
+      .approveProposal(0, new BN(10_000_000), null)
+      .accounts({ approver: human, ...payoutAccounts(proposalPda) })
+      .rpc();
+
+    const scheduled = await program.account.proposal.fetch(proposalPda);
+    expect(scheduled.executed).to.be.false;
+    expect(scheduled.approvedAt).to.not.be.null;
+    expect(scheduled.executableAt.toNumber()).to.equal(scheduled.approvedAt.toNumber() + 2);
+    const unchanged = await getAccount(provider.connection, recipientAta);
+    expect(unchanged.amount.toString()).to.equal(before.amount.toString());
+
+    try {
+      await program.methods
+        .executeProposal()
+        .accounts({ executor: recipient.publicKey, proposerRole: null, ...payoutAccounts(proposalPda) })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ExecutionDelayNotElapsed");
+    }
+
+    await new Promise((r) => setTimeout(r, 3000));
+    await program.methods
+      .executeProposal()
+      .accounts({ executor: recipient.publicKey, proposerRole: null, ...payoutAccounts(proposalPda) })
+      .signers([recipient])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(10_000_000);
+    expect((await program.account.proposal.fetch(proposalPda)).executed).to.be.true;
+    await expectReconciled();
+  });
+
+  it("A guardian cancels a scheduled proposal inside the delay window", async () => {
+    const guardian = Keypair.generate();
+    await program.methods.setGuardians([guardian.publicKey], 1, new BN(0)).accounts({ human, vault }).rpc();
+    await program.methods.setExecutionDelay(new BN(5)).accounts({ human, vault }).rpc();
+    const proposalPda = await proposeForDelay("Suspicious payout");
+
+    // Guardians can't cancel before approval
+    try {
+      await program.methods
+        .cancelProposal({ suspicious: {} }, "")
+        .accounts({ signer: guardian.publicKey, vault, proposal: proposalPda })
+        .signers([guardian])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("OnlyHuman");
+    }
+
+    await program.methods
+      .approveProposal(0, new BN(10_000_000), null)
+      .accounts({ approver: human, ...payoutAccounts(proposalPda) })
+      .rpc();
+    await program.methods
+      .cancelProposal({ suspicious: {} }, "")
+      .accounts({ signer: guardian.publicKey, vault, proposal: proposalPda })
+      .signers([guardian])
+      .rpc();
+
+    const cancelled = await program.account.proposal.fetch(proposalPda);
+    expect(cancelled.cancelled).to.be.true;
+
+    // Once executable, only the human can still cancel
+    const lateProposal = await proposeForDelay("Left too long");
+    await program.methods
+      .approveProposal(0, new BN(10_000_000), null)
+      .accounts({ approver: human, ...payoutAccounts(lateProposal) })
+      .rpc();
+
+    // Lowering the delay waits for the current one to pass
+    await program.methods.setExecutionDelay(new BN(0)).accounts({ human, vault }).rpc();
+    const v = await program.account.vault.fetch(vault);
+    expect(v.executionDelay.toNumber()).to.equal(5);
+    expect(v.pendingExecutionDelay.delay.toNumber()).to.equal(0);
+    await new Promise((r) => setTimeout(r, 6000));
+
+    try {
+      await program.methods
+        .cancelProposal({ suspicious: {} }, "")
+        .accounts({ signer: guardian.publicKey, vault, proposal: lateProposal })
+        .signers([guardian])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("OnlyHuman");
+    }
+    await program.methods
+      .cancelProposal({ notNeeded: {} }, "")
+      .accounts({ signer: human, vault, proposal: lateProposal })
+      .rpc();
+    await program.methods.setGuardians([], 0, new BN(0)).accounts({ human, vault }).rpc();
+  });
+
+  it("Proposal in the optimistic band executes after the veto period without approval", async () => {
+    await program.methods
+      .setOptimisticBand(new BN(150_000_000), new BN(2))
+      .accounts({ human, vault })
+      .rpc();
+
+    const inBand = await proposeForDelay("Mid-size, optimistic");
+    await program.methods
+      .amendProposal(new BN(120_000_000), null)
+      .accounts({ agent: agent.publicKey, vault, agentRole: null, proposal: inBand, recipient: null, recipientAta: null })
+      .signers([agent])
+      .rpc();
+    const proposal = await program.account.proposal.fetch(inBand);
+    expect(proposal.optimistic).to.be.true;
+    expect(proposal.approvedAt).to.be.null;
+    expect(proposal.executableAt).to.not.be.null;
+
+    try {
+      await program.methods
+        .executeProposal()
+        .accounts({ executor: recipient.publicKey, proposerRole: null, ...payoutAccounts(inBand) })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ExecutionDelayNotElapsed");
+    }
+
+    await new Promise((r) => setTimeout(r, 3000));
+    const before = await getAccount(provider.connection, recipientAta);
+    await program.methods
+      .executeProposal()
+      .accounts({ executor: recipient.publicKey, proposerRole: null, ...payoutAccounts(inBand) })
+      .signers([recipient])
+      .rpc();
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(120_000_000);
+    await expectReconciled();
+  });
+
+  it("Proposal above the optimistic cap still needs approval (ProposalNotScheduled)", async () => {
+    const aboveCap = await proposeForDelay("Large, needs approval");
+    await program.methods
+      .amendProposal(new BN(200_000_000), null)
+      .accounts({ agent: agent.publicKey, vault, agentRole: null, proposal: aboveCap, recipient: null, recipientAta: null })
+      .signers([agent])
+      .rpc();
+    expect((await program.account.proposal.fetch(aboveCap)).optimistic).to.be.false;
+
+    try {
+      await program.methods
+        .executeProposal()
+        .accounts({ executor: recipient.publicKey, proposerRole: null, ...payoutAccounts(aboveCap) })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ProposalNotScheduled");
+    }
+
+    await program.methods
+      .withdrawProposal()
+      .accounts({ agent: agent.publicKey, vault, proposal: aboveCap, agentLedger: null })
+      .signers([agent])
+      .rpc();
+    await program.methods.setOptimisticBand(new BN(0), new BN(0)).accounts({ human, vault }).rpc();
+  });
+
+  it("Optimistic proposal can't execute once the band no longer covers it (OutsideOptimisticBand)", async () => {
+    await program.methods
+      .setOptimisticBand(new BN(150_000_000), new BN(2))
+      .accounts({ human, vault })
+      .rpc();
+    const inBand = await proposeForDelay("Optimistic, then band closed");
+    await program.methods
+      .amendProposal(new BN(120_000_000), null)
+      .accounts({ agent: agent.publicKey, vault, agentRole: null, proposal: inBand, recipient: null, recipientAta: null })
+      .signers([agent])
+      .rpc();
+    expect((await program.account.proposal.fetch(inBand)).optimistic).to.be.true;
+
+    await program.methods.setOptimisticBand(new BN(0), new BN(0)).accounts({ human, vault }).rpc();
+    await new Promise((r) => setTimeout(r, 3000));
+    try {
+      await program.methods
+        .executeProposal()
+        .accounts({ executor: recipient.publicKey, proposerRole: null, ...payoutAccounts(inBand) })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("OutsideOptimisticBand");
+    }
+
+    await program.methods
+      .withdrawProposal()
+      .accounts({ agent: agent.publicKey, vault, proposal: inBand, agentLedger: null })
+      .signers([agent])
+      .rpc();
+  });
+
+  it("Amending into the optimistic band late moves the expiry past the new veto period", async () => {
+    await program.methods.setProposalTtl(new BN(10)).accounts({ human, vault }).rpc();
+    await program.methods
+      .setOptimisticBand(new BN(150_000_000), new BN(8))
+      .accounts({ human, vault })
+      .rpc();
+    const late = await proposeForDelay("Amended late");
+    const created = await program.account.proposal.fetch(late);
+
+    await new Promise((r) => setTimeout(r, 4000));
+    await program.methods
+      .amendProposal(new BN(120_000_000), null)
+      .accounts({ agent: agent.publicKey, vault, agentRole: null, proposal: late, recipient: null, recipientAta: null })
+      .signers([agent])
+      .rpc();
+    const amended = await program.account.proposal.fetch(late);
+    expect(amended.optimistic).to.be.true;
+    expect(amended.executableAt.toNumber()).to.be.greaterThan(created.expiresAt.toNumber());
+    expect(amended.expiresAt.toNumber()).to.be.greaterThan(amended.executableAt.toNumber());
+
+    await program.methods
+      .withdrawProposal()
+      .accounts({ agent: agent.publicKey, vault, proposal: late, agentLedger: null })
+      .signers([agent])
+      .rpc();
+    await program.methods.setOptimisticBand(new BN(0), new BN(0)).accounts({ human, vault }).rpc();
+    await program.methods.setProposalTtl(new BN(0)).accounts({ human, vault }).rpc();
+  });
+
+  it("Veto period must be shorter than the proposal TTL (InvalidOptimisticBand)", async () => {
+    await program.methods.setProposalTtl(new BN(10)).accounts({ human, vault }).rpc();
+    try {
+      await program.methods
+        .setOptimisticBand(new BN(150_000_000), new BN(10))
+        .accounts({ human, vault })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidOptimisticBand");
+    }
+    await program.methods.setProposalTtl(new BN(0)).accounts({ human, vault }).rpc();
+  });
+
+  // --- Nonce proposal tests ---
+
+  function nonceProposalPda(nonce: BN) {
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    return proposalPda;
+  }
+
+  let agentLedger: PublicKey;
+
+  it("Agent opens its ledger before proposing with nonces", async () => {
+    [agentLedger] = PublicKey.findProgramAddressSync(
+      [Buffer.from("agent_ledger"), vault.toBuffer(), agent.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .openAgentLedger()
+      .accounts({ agent: agent.publicKey, vault, agentRole: null, agentLedger, systemProgram: SystemProgram.programId })
+      .signers([agent])
+      .rpc();
+    const ledger = await program.account.agentLedger.fetch(agentLedger);
+    expect(ledger.agent.toBase58()).to.equal(agent.publicKey.toBase58());
+    expect(ledger.openProposals).to.equal(0);
+  });
+
+  function proposeWithNonce(nonce: BN, memo: string) {
+    return program.methods
+      .proposeWithNonce(nonce, new BN(10_000_000), memo)
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: nonceProposalPda(nonce),
+        agentRole: null,
+        agentLedger,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  it("Agent submits nonce-keyed proposals in parallel without touching the vault counter", async () => {
+    const flag = new BN(1).shln(63);
+    const first = flag.addn(1);
+    const second = flag.addn(2);
+    const countBefore = (await program.account.vault.fetch(vault)).proposalCount.toNumber();
+
+    await Promise.all([proposeWithNonce(first, "Parallel 1"), proposeWithNonce(second, "Parallel 2")]);
+
+    const proposal = await program.account.proposal.fetch(nonceProposalPda(second));
+    expect(proposal.proposalId.toString()).to.equal(second.toString());
+    expect(proposal.memo).to.equal("Parallel 2");
+    expect((await program.account.vault.fetch(vault)).proposalCount.toNumber()).to.equal(countBefore);
+    // Numbered from the agent's ledger instead, in whichever order they landed
+    const sequences = await Promise.all(
+      [first, second].map(async (n) => (await program.account.proposal.fetch(nonceProposalPda(n))).sequence.toNumber())
+    );
+    expect(sequences.sort()).to.deep.equal([1, 2]);
+    expect((await program.account.agentLedger.fetch(agentLedger)).proposalCount.toNumber()).to.equal(2);
+
+    // Nonces without the top bit would collide with sequential IDs
+    try {
+      await proposeWithNonce(new BN(countBefore + 5), "Collides");
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidProposalNonce");
+    }
+
+    for (const nonce of [first, second]) {
+      await program.methods
+        .withdrawProposal()
+        .accounts({ agent: agent.publicKey, vault, proposal: nonceProposalPda(nonce), agentLedger })
+        .signers([agent])
+        .rpc();
+    }
+  });
+
+  it("A vote for a withdrawn nonce proposal can't land on its replacement (ProposalAmountMismatch)", async () => {
+    const nonce = new BN(1).shln(63).addn(3);
+    await proposeWithNonce(nonce, "Original");
+    const originalSequence = (await program.account.proposal.fetch(nonceProposalPda(nonce))).sequence.toNumber();
+    await program.methods
+      .withdrawProposal()
+      .accounts({ agent: agent.publicKey, vault, proposal: nonceProposalPda(nonce), agentLedger })
+      .signers([agent])
+      .rpc();
+
+    // Same nonce, same recipient, larger amount
+    await program.methods
+      .proposeWithNonce(nonce, new BN(90_000_000), "Replacement")
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: nonceProposalPda(nonce),
+        agentRole: null,
+        agentLedger,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    // The ID is reused, the display number isn't
+    const replacement = await program.account.proposal.fetch(nonceProposalPda(nonce));
+    expect(replacement.sequence.toNumber()).to.equal(originalSequence + 1);
+
+    try {
+      await program.methods
+        .approveProposal(0, new BN(10_000_000), null)
+        .accounts({ approver: human, ...payoutAccounts(nonceProposalPda(nonce)) })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ProposalAmountMismatch");
+    }
+
+    await program.methods
+      .withdrawProposal()
+      .accounts({ agent: agent.publicKey, vault, proposal: nonceProposalPda(nonce), agentLedger })
+      .signers([agent])
+      .rpc();
+  });
+
+  // --- Batch proposal tests ---
+
+  it("Approves several proposals in one transaction", async () => {
+    const first = await proposeForDelay("Batch 1");
+    const second = await proposeForDelay("Batch 2");
+    const before = await getAccount(provider.connection, recipientAta);
+
+    await program.methods
+      .approveProposals([0, 0], [new BN(10_000_000), new BN(10_000_000)])
+      .accounts({
+        approver: human,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: first, isSigner: false, isWritable: true },
+        { pubkey: recipientAta, isSigner: false, isWritable: true },
+        { pubkey: second, isSigner: false, isWritable: true },
+        { pubkey: recipientAta, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(20_000_000);
+    expect((await program.account.proposal.fetch(first)).executed).to.be.true;
+    expect((await program.account.proposal.fetch(second)).executed).to.be.true;
+    await expectReconciled();
+  });
+
+  it("Cancels several proposals in one transaction", async () => {
+    const first = await proposeForDelay("Batch cancel 1");
+    const second = await proposeForDelay("Batch cancel 2");
+
+    await program.methods
+      .cancelProposals({ notNeeded: {} }, "Already paid")
+      .accounts({ signer: human, vault })
+      .remainingAccounts([
+        { pubkey: first, isSigner: false, isWritable: true },
+        { pubkey: second, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    expect((await program.account.proposal.fetch(first)).cancelled).to.be.true;
+    const cancelled = await program.account.proposal.fetch(second);
+    expect(cancelled.cancelled).to.be.true;
+    expect(cancelled.cancelReason).to.deep.equal({ notNeeded: {} });
+    expect(cancelled.cancelNote).to.equal("Already paid");
+
+    // Already-cancelled proposals fail the whole batch
+    try {
+      await program.methods
+        .cancelProposals({ notNeeded: {} }, "")
+        .accounts({ signer: human, vault })
+        .remainingAccounts([{ pubkey: first, isSigner: false, isWritable: true }])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("ProposalAlreadyCancelled");
+    }
+  });
+
+  // --- Whitelist tests ---
+
+  let whitelistPda: PublicKey;
+
+  it("Human adds recipient to whitelist", async () => {
+    [whitelistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("whitelist"), vault.toBuffer(), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .addWhitelist(recipient.publicKey, UNLIMITED_WHITELIST, null)
+      .accounts({
+        human,
+        vault,
+        whitelistEntry: whitelistPda,
+        agentRole: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const wl = await program.account.whitelistEntry.fetch(whitelistPda);
+    expect(wl.address.toString()).to.equal(recipient.publicKey.toString());
+  });
+
+  it("Agent sends over tier2_max to whitelisted recipient (200 USDC)", async () => {
+    const before = await getAccount(provider.connection, recipientAta);
+
+    await program.methods
+      .sendUsdc(new BN(200_000_000), false, "Test payment", null)
+      .accounts({
+        signer: agent.publicKey,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: whitelistPda,
+        agentRole: null,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(200_000_000);
+  });
+
+  it("Human caps the whitelisted recipient per transaction", async () => {
+    await program.methods
+      .updateWhitelist({
+        maxPerTx: new BN(150_000_000),
+        maxPerPeriod: new BN(500_000_000),
+        periodSeconds: new BN(86_400),
+      })
+      .accounts({ human, vault, whitelistEntry: whitelistPda })
+      .rpc();
+
+    const wl = await program.account.whitelistEntry.fetch(whitelistPda);
+    expect(wl.maxPerTx.toNumber()).to.equal(150_000_000);
+    expect(wl.periodBudget.limit.toNumber()).to.equal(500_000_000);
+  });
+
+  it("Whitelisted send over max_per_tx fails (WhitelistTxLimitExceeded)", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(200_000_000), false, "Test payment", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: whitelistPda,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("WhitelistTxLimitExceeded");
+    }
+  });
+
+  it("Human removes recipient from whitelist", async () => {
+    await program.methods
+      .removeWhitelist()
+      .accounts({ human, vault, whitelistEntry: whitelistPda })
+      .rpc();
+
+    try {
+      await program.account.whitelistEntry.fetch(whitelistPda);
+      expect.fail("Should be closed");
+    } catch (e: any) {
+      expect(e.message).to.include("Account does not exist");
+    }
+  });
+
+  it("Whitelist entry added with a delay is pending and ignored by send_usdc", async () => {
+    await program.methods
+      .setWhitelistDelay(new BN(3))
+      .accounts({ human, vault })
+      .rpc();
+
+    [whitelistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("whitelist"), vault.toBuffer(), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .addWhitelist(recipient.publicKey, UNLIMITED_WHITELIST, null)
+      .accounts({
+        human,
+        vault,
+        whitelistEntry: whitelistPda,
+        agentRole: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const wl = await program.account.whitelistEntry.fetch(whitelistPda);
+    expect(wl.activeFrom.toNumber() - wl.addedAt.toNumber()).to.equal(3);
+
+    // Pending entry doesn't apply, so the normal tier check rejects 200 USDC
+    try {
+      await program.methods
+        .sendUsdc(new BN(200_000_000), false, "Test payment", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: whitelistPda,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("TierTooHigh");
+    }
+  });
+
+  it("Human cancels the pending whitelist entry", async () => {
+    await program.methods
+      .cancelWhitelist()
+      .accounts({ human, vault, whitelistEntry: whitelistPda })
+      .rpc();
+
+    try {
+      await program.account.whitelistEntry.fetch(whitelistPda);
+      expect.fail("Should be closed");
+    } catch (e: any) {
+      expect(e.message).to.include("Account does not exist");
+    }
+
+    // Lowering the delay waits out the current one
+    await program.methods
+      .setWhitelistDelay(new BN(0))
+      .accounts({ human, vault })
+      .rpc();
+    const v = await program.account.vault.fetch(vault);
+    expect(v.whitelistDelay.toNumber()).to.equal(3);
+    expect(v.pendingWhitelistDelay.delay.toNumber()).to.equal(0);
+    await new Promise((r) => setTimeout(r, 4000));
+  });
+
+  it("Agent over-tier1 send fails after whitelist removal", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(75_000_000), false, "Test payment", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("NotEmergency");
+    }
+  });
+
+  // --- Admin tests ---
+
+  it("Human updates tiers", async () => {
+    const newT1 = new BN(75_000_000);
+    const newT2 = new BN(150_000_000);
+
+    await program.methods
+      .setTiers(newT1, newT2)
+      .accounts({ human, vault })
+      .rpc();
+
+    const v = await program.account.vault.fetch(vault);
+    expect(v.tier1Max.toString()).to.equal(newT1.toString());
+    expect(v.tier2Max.toString()).to.equal(newT2.toString());
+  });
+
+  it("Human rotates the agent key; vault address and state carry over", async () => {
+    const newAgent = Keypair.generate();
+    const beforeVault = await program.account.vault.fetch(vault);
+
+    await program.methods
+      .rotateAgent(newAgent.publicKey)
+      .accounts({ human, vault })
+      .rpc();
+
+    const rotated = await program.account.vault.fetch(vault);
+    expect(rotated.agent.toString()).to.equal(newAgent.publicKey.toString());
+    expect(rotated.seedAgent.toString()).to.equal(agent.publicKey.toString());
+    expect(rotated.proposalCount.toString()).to.equal(beforeVault.proposalCount.toString());
+
+    // Old key is locked out, new key sends from the same vault
+    try {
+      await program.methods
+        .sendUsdc(new BN(1_000_000), false, "Test payment", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("OnlyAgentOrHuman");
+    }
+
+    await program.methods
+      .sendUsdc(new BN(1_000_000), false, "Test payment", null)
+      .accounts({
+        signer: newAgent.publicKey,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: null,
+        agentRole: null,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([newAgent])
+      .rpc();
+
+    // Rotate back so the remaining tests keep using the original agent
+    await program.methods
+      .rotateAgent(agent.publicKey)
+      .accounts({ human, vault })
+      .rpc();
+  });
+
+  it("Human migrates a legacy vault, then a whitelist entry and proposal left out of it", async () => {
+    // Loaded from tests/fixtures in the pre-rotation layout (see Anchor.toml)
+    const legacyHuman = Keypair.fromSecretKey(
+      Uint8Array.from(JSON.parse(fs.readFileSync(path.join(__dirname, "fixtures/legacy-human.json"), "utf-8")))
+    );
+    const legacyAgent = new PublicKey("US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx");
+    const legacyVault = new PublicKey("A9mBdhm9Ew86CMTpGGnj9S6JqTbGjiGqYbWdRro5uZrk");
+    const legacyEntry = new PublicKey("HN4VVgc4zgRaikrwXKbrKsZYyj2pygoBjqSxQErXksoq");
+    const legacyProposal = new PublicKey("b1PBMLiU5dN3aLzxEh9rDdaYzEHcHcFk4rxtgTHyakp");
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(legacyHuman.publicKey, 1e9)
+    );
+
+    // The whitelist entry is left out, as an older client would have done
+    await program.methods
+      .migrateVault()
+      .accounts({ human: legacyHuman.publicKey, vault: legacyVault, systemProgram: SystemProgram.programId })
+      .remainingAccounts([{ pubkey: legacyProposal, isWritable: true, isSigner: false }])
+      .signers([legacyHuman])
+      .rpc();
+    let v = await program.account.vault.fetch(legacyVault);
+    expect(v.seedAgent.toString()).to.equal(legacyAgent.toString());
+    expect(v.proposalCount.toNumber()).to.equal(1);
+    expect(v.openProposals).to.equal(1);
+    expect(v.whitelistEntries).to.equal(0);
+    const proposal = await program.account.proposal.fetch(legacyProposal);
+    expect(proposal.proposer.toString()).to.equal(legacyAgent.toString());
+    expect(proposal.amount.toNumber()).to.equal(200_000_000);
+    expect(proposal.memo).to.equal("Legacy proposal");
+
+    // A migrated vault can't be migrated again
+    try {
+      await program.methods
+        .migrateVault()
+        .accounts({ human: legacyHuman.publicKey, vault: legacyVault, systemProgram: SystemProgram.programId })
+        .remainingAccounts([{ pubkey: legacyEntry, isWritable: true, isSigner: false }])
+        .signers([legacyHuman])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("NotLegacyAccount");
+    }
+
+    await program.methods
+      .migrateVaultAccounts()
+      .accounts({ human: legacyHuman.publicKey, vault: legacyVault, systemProgram: SystemProgram.programId })
+      .remainingAccounts([{ pubkey: legacyEntry, isWritable: true, isSigner: false }])
+      .signers([legacyHuman])
+      .rpc();
+    v = await program.account.vault.fetch(legacyVault);
+    expect(v.whitelistEntries).to.equal(1);
+    expect(v.openProposals).to.equal(1);
+    const entry = await program.account.whitelistEntry.fetch(legacyEntry);
+    expect(entry.vault.toString()).to.equal(legacyVault.toString());
+    expect(entry.maxPerTx.toNumber()).to.equal(0);
+
+    // Already-migrated children are rejected
+    try {
+      await program.methods
+        .migrateVaultAccounts()
+        .accounts({ human: legacyHuman.publicKey, vault: legacyVault, systemProgram: SystemProgram.programId })
+        .remainingAccounts([{ pubkey: legacyProposal, isWritable: true, isSigner: false }])
+        .signers([legacyHuman])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("NotLegacyAccount");
+    }
+  });
+
+  it("Human hands ownership to a new key in two steps", async () => {
+    const newHuman = Keypair.generate();
+
+    await program.methods
+      .proposeHumanTransfer(newHuman.publicKey)
+      .accounts({ human, vault })
+      .rpc();
+    let v = await program.account.vault.fetch(vault);
+    expect(v.pendingHuman.toString()).to.equal(newHuman.publicKey.toString());
+    expect(v.human.toString()).to.equal(human.toString());
+
+    await program.methods
+      .acceptHumanTransfer()
+      .accounts({ newHuman: newHuman.publicKey, vault })
+      .signers([newHuman])
+      .rpc();
+    v = await program.account.vault.fetch(vault);
+    expect(v.human.toString()).to.equal(newHuman.publicKey.toString());
+    expect(v.seedHuman.toString()).to.equal(human.toString());
+    expect(v.pendingHuman).to.be.null;
+
+    // Hand it back for the remaining tests
+    await program.methods
+      .proposeHumanTransfer(human)
+      .accounts({ human: newHuman.publicKey, vault })
+      .signers([newHuman])
+      .rpc();
+    await program.methods
+      .acceptHumanTransfer()
+      .accounts({ newHuman: human, vault })
+      .rpc();
+  });
+
+  it("Guardians recover the vault; the human can veto first", async () => {
+    const guardian1 = Keypair.generate();
+    const guardian2 = Keypair.generate();
+    const recoveredHuman = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(guardian1.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .setGuardians([guardian1.publicKey, guardian2.publicKey], 2, new BN(0))
+      .accounts({ human, vault })
+      .rpc();
+
+    const [recoveryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recovery"), vault.toBuffer()],
+      program.programId
+    );
+    const initiate = () =>
+      program.methods
+        .initiateRecovery(recoveredHuman.publicKey)
+        .accounts({
+          guardian: guardian1.publicKey,
+          vault,
+          recoveryRequest: recoveryPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([guardian1])
+        .rpc();
+    const execute = () =>
+      program.methods
+        .executeRecovery()
+        .accounts({ executor: human, vault, recoveryRequest: recoveryPda, initiator: guardian1.publicKey })
+        .rpc();
+
+    // One vote is below the threshold, so execution is refused
+    await initiate();
+    try {
+      await execute();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("RecoveryNotReady");
+    }
+
+    await program.methods
+      .vetoRecovery()
+      .accounts({ human, vault, recoveryRequest: recoveryPda, initiator: guardian1.publicKey })
+      .rpc();
+
+    // Second attempt reaches 2-of-2 and executes after the (zero) timelock
+    await initiate();
+    await program.methods
+      .approveRecovery()
+      .accounts({ guardian: guardian2.publicKey, vault, recoveryRequest: recoveryPda })
+      .signers([guardian2])
+      .rpc();
+    await execute();
+
+    const v = await program.account.vault.fetch(vault);
+    expect(v.human.toString()).to.equal(recoveredHuman.publicKey.toString());
+
+    // Recovered owner hands the vault back and clears the guardians
+    await program.methods
+      .proposeHumanTransfer(human)
+      .accounts({ human: recoveredHuman.publicKey, vault })
+      .signers([recoveredHuman])
+      .rpc();
+    await program.methods
+      .acceptHumanTransfer()
+      .accounts({ newHuman: human, vault })
+      .rpc();
+    await program.methods
+      .setGuardians([], 0, new BN(0))
+      .accounts({ human, vault })
+      .rpc();
+  });
+
+  it("Human pauses vault", async () => {
+    await program.methods.pause().accounts({ human, vault }).rpc();
+    const v = await program.account.vault.fetch(vault);
+    expect(v.paused).to.be.true;
+  });
+
+  it("Agent send fails when paused", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(10_000_000), false, "Test payment", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("VaultPaused");
+    }
+  });
+
+  it("Human can still send when paused", async () => {
+    const before = await getAccount(provider.connection, recipientAta);
+
+    await program.methods
+      .sendUsdc(new BN(10_000_000), false, "Test payment", null)
+      .accounts({
+        signer: human,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: null,
+        agentRole: null,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(10_000_000);
+  });
+
+  it("Human unpauses vault", async () => {
+    await program.methods.unpause().accounts({ human, vault }).rpc();
+    const v = await program.account.vault.fetch(vault);
+    expect(v.paused).to.be.false;
+  });
+
+  it("Agent send succeeds after unpause", async () => {
+    const before = await getAccount(provider.connection, recipientAta);
+
+    await program.methods
+      .sendUsdc(new BN(10_000_000), false, "Test payment", null)
+      .accounts({
+        signer: agent.publicKey,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: null,
+        agentRole: null,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(10_000_000);
+  });
+
+  // --- Budget tests ---
+
+  it("Human sets daily and weekly budgets", async () => {
+    await program.methods
+      .setBudgets(new BN(200_000_000), new BN(1_000_000_000))
+      .accounts({ human, vault })
+      .rpc();
+
+    const v = await program.account.vault.fetch(vault);
+    expect(v.dailyBudget.limit.toNumber()).to.equal(200_000_000);
+    expect(v.weeklyBudget.limit.toNumber()).to.equal(1_000_000_000);
+  });
+
+  it("Daily budget larger than weekly fails (InvalidBudgets)", async () => {
+    try {
+      await program.methods
+        .setBudgets(new BN(2_000_000_000), new BN(1_000_000_000))
+        .accounts({ human, vault })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidBudgets");
+    }
+  });
+
+  it("Agent send over remaining daily budget fails (DailyBudgetExceeded)", async () => {
+    // Leave exactly 5 USDC of daily budget given what was spent so far
+    const v = await program.account.vault.fetch(vault);
+    const dailyLimit = v.dailyBudget.spent.add(new BN(5_000_000));
+    await program.methods
+      .setBudgets(dailyLimit, new BN(0))
+      .accounts({ human, vault })
+      .rpc();
+
+    try {
+      await program.methods
+        .sendUsdc(new BN(10_000_000), false, "Test payment", null)
+        .accounts({
+          signer: agent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: null,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("DailyBudgetExceeded");
+    }
+
+    await program.methods
+      .sendUsdc(new BN(5_000_000), false, "Test payment", null)
+      .accounts({
+        signer: agent.publicKey,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: null,
+        agentRole: null,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+
+    const after = await program.account.vault.fetch(vault);
+    expect(after.dailyBudget.spent.toString()).to.equal(dailyLimit.toString());
+
+    // Lift budgets for the remaining tests
+    await program.methods
+      .setBudgets(new BN(0), new BN(0))
+      .accounts({ human, vault })
+      .rpc();
+  });
+
+  // --- Multi-agent tests ---
+
+  let secondAgent: Keypair;
+  let secondAgentRole: PublicKey;
+
+  it("Human registers a second agent with its own tiers", async () => {
+    secondAgent = Keypair.generate();
+    [secondAgentRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("agent_role"), vault.toBuffer(), secondAgent.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .addAgent(secondAgent.publicKey, new BN(10_000_000), new BN(20_000_000), false)
+      .accounts({
+        human,
+        vault,
+        agentRole: secondAgentRole,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const role = await program.account.agentRole.fetch(secondAgentRole);
+    expect(role.agent.toString()).to.equal(secondAgent.publicKey.toString());
+    expect(role.tier1Max.toNumber()).to.equal(10_000_000);
+    expect(role.canPropose).to.be.false;
+  });
+
+  it("Second agent sends within its own tier 1", async () => {
+    const before = await getAccount(provider.connection, recipientAta);
+
+    await program.methods
+      .sendUsdc(new BN(5_000_000), false, "Test payment", null)
+      .accounts({
+        signer: secondAgent.publicKey,
+        vault,
+        usdcMint,
+        vaultUsdcAta,
+        recipientAta,
+        recipient: null,
+        whitelistEntry: null,
+        agentRole: secondAgentRole,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([secondAgent])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    // The unused fee reserve comes back too
+    expect(Number(after.amount) - Number(before.amount)).to.equal(5_012_500);
+  });
+
+  it("Second agent is held to its own tier 1, not the vault's", async () => {
+    try {
+      await program.methods
+        .sendUsdc(new BN(15_000_000), false, "Test payment", null)
+        .accounts({
+          signer: secondAgent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: secondAgentRole,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([secondAgent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("NotEmergency");
+    }
+  });
+
+  it("Paused second agent can't send", async () => {
+    await program.methods
+      .updateAgent(new BN(10_000_000), new BN(20_000_000), true, false)
+      .accounts({ human, vault, agentRole: secondAgentRole })
+      .rpc();
+
+    try {
+      await program.methods
+        .sendUsdc(new BN(5_000_000), false, "Test payment", null)
+        .accounts({
+          signer: secondAgent.publicKey,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          recipientAta,
+          recipient: null,
+          whitelistEntry: null,
+          agentRole: secondAgentRole,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+        })
+        .signers([secondAgent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("AgentPaused");
+    }
+  });
+
+  it("Human removes the second agent", async () => {
+    await program.methods
+      .removeAgent()
+      .accounts({ human, vault, agentRole: secondAgentRole })
+      .rpc();
+
+    try {
+      await program.account.agentRole.fetch(secondAgentRole);
+      expect.fail("Should be closed");
+    } catch (e: any) {
+      expect(e.message).to.include("Account does not exist");
+    }
+  });
+
+  // --- Additional mint tests ---
+
+  let eurcMint: PublicKey;
+  let mintConfigPda: PublicKey;
+  let vaultEurcAta: PublicKey;
+  let recipientEurcAta: PublicKey;
+  let buybackEurcAta: PublicKey;
+
+  function eurcAccounts() {
+    return {
+      mint: eurcMint,
+      mintConfig: mintConfigPda,
+      vaultTokenAta: vaultEurcAta,
+      recipientAta: recipientEurcAta,
+      agentRole: null,
+      protocolConfig,
+      buybackAta,
+      tokenBuybackAta: buybackEurcAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      memoProgram: MEMO_PROGRAM_ID,
+    };
+  }
+
+  it("Human enables a second mint with its own tiers", async () => {
+    eurcMint = await createMint(provider.connection, mintAuthority, mintAuthority.publicKey, null, 6);
+    [mintConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_config"), vault.toBuffer(), eurcMint.toBuffer()],
+      program.programId
+    );
+    vaultEurcAta = getAssociatedTokenAddressSync(eurcMint, vault, true);
+    recipientEurcAta = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, mintAuthority, eurcMint, recipient.publicKey
+    )).address;
+    buybackEurcAta = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, mintAuthority, eurcMint, buybackWallet.publicKey
+    )).address;
+
+    await program.methods
+      .addMint(new BN(20_000_000), new BN(40_000_000))
+      .accounts({
+        human,
+        vault,
+        mint: eurcMint,
+        mintConfig: mintConfigPda,
+        vaultTokenAta: vaultEurcAta,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const config = await program.account.mintConfig.fetch(mintConfigPda);
+    expect(config.mint.toString()).to.equal(eurcMint.toString());
+    expect(config.vaultAta.toString()).to.equal(vaultEurcAta.toString());
+    expect(config.tier1Max.toNumber()).to.equal(20_000_000);
+
+    await mintTo(provider.connection, mintAuthority, eurcMint, vaultEurcAta, mintAuthority, 500_000_000);
+  });
+
+  it("USDC can't be added as a separate mint (CannotConfigureUsdcMint)", async () => {
+    const [usdcConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_config"), vault.toBuffer(), usdcMint.toBuffer()],
+      program.programId
+    );
+    try {
+      await program.methods
+        .addMint(new BN(1), new BN(1))
+        .accounts({
+          human,
+          vault,
+          mint: usdcMint,
+          mintConfig: usdcConfig,
+          vaultTokenAta: vaultUsdcAta,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("CannotConfigureUsdcMint");
+    }
+  });
+
+  it("Agent sends the second mint within its tier 1, fee all to buyback", async () => {
+    const beforeRecipient = await getAccount(provider.connection, recipientEurcAta);
+    const beforeBuyback = await getAccount(provider.connection, buybackEurcAta);
+
+    await program.methods
+      .sendToken(new BN(20_000_000), false, "Hosting, EUR invoice", null)
+      .accounts({ signer: agent.publicKey, vault, ...eurcAccounts() })
+      .signers([agent])
+      .rpc();
+
+    const afterRecipient = await getAccount(provider.connection, recipientEurcAta);
+    const afterBuyback = await getAccount(provider.connection, buybackEurcAta);
+    expect(Number(afterRecipient.amount) - Number(beforeRecipient.amount)).to.equal(20_000_000);
+    // Fee = 20_000_000 * 25 / 10000 = 50_000
+    expect(Number(afterBuyback.amount) - Number(beforeBuyback.amount)).to.equal(50_000);
+  });
+
+  it("Second mint's tiers apply, not the vault's USDC tiers (TierTooHigh)", async () => {
+    try {
+      await program.methods
+        .sendToken(new BN(45_000_000), true, "Over the EURC tiers", null)
+        .accounts({ signer: agent.publicKey, vault, ...eurcAccounts() })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("TierTooHigh");
+    }
+  });
+
+  it("Second mint's daily budget is enforced (DailyBudgetExceeded)", async () => {
+    await program.methods
+      .setMintLimits(new BN(20_000_000), new BN(40_000_000), new BN(30_000_000), new BN(0))
+      .accounts({ human, vault, mintConfig: mintConfigPda })
+      .rpc();
+
+    try {
+      await program.methods
+        .sendToken(new BN(20_000_000), false, "Hosting, EUR invoice", null)
+        .accounts({ signer: agent.publicKey, vault, ...eurcAccounts() })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("DailyBudgetExceeded");
+    }
+
+    await program.methods
+      .setMintLimits(new BN(20_000_000), new BN(40_000_000), new BN(0), new BN(0))
+      .accounts({ human, vault, mintConfig: mintConfigPda })
+      .rpc();
+  });
+
+  it("Agent proposes in the second mint and the human approves it", async () => {
+    const v = await program.account.vault.fetch(vault);
+    const [eurcProposal] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), vault.toBuffer(), v.proposalCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .propose(new BN(60_000_000), "Euro invoice")
+      .accounts({
+        agent: agent.publicKey,
+        vault,
+        agentRole: null,
+        mintConfig: mintConfigPda,
+        recipient: recipient.publicKey,
+        recipientAta: recipientEurcAta,
+        proposal: eurcProposal,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    const created = await program.account.proposal.fetch(eurcProposal);
+    expect(created.mint.toString()).to.equal(eurcMint.toString());
+
+    // The batch path only pays out USDC
+    try {
+      await program.methods
+        .approveProposals([0], [new BN(60_000_000)])
+        .accounts({
+          approver: human,
+          vault,
+          usdcMint,
+          vaultUsdcAta,
+          ...feeAccounts(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: eurcProposal, isSigner: false, isWritable: true },
+          { pubkey: recipientEurcAta, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("BatchUsdcOnly");
+    }
+
+    const before = await getAccount(provider.connection, recipientEurcAta);
+    await program.methods
+      .approveProposal(0, new BN(60_000_000), null)
+      .accounts({
+        approver: human,
+        vault,
+        proposal: eurcProposal,
+        mint: eurcMint,
+        mintConfig: mintConfigPda,
+        vaultTokenAta: vaultEurcAta,
+        recipientAta: recipientEurcAta,
+        recipient: null,
+        ...feeAccounts(),
+        tokenBuybackAta: buybackEurcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientEurcAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(60_000_000);
+    const proposal = await program.account.proposal.fetch(eurcProposal);
+    expect(proposal.executed).to.be.true;
+  });
+
+  it("Mint with a balance can't be removed (MintBalanceNotEmpty)", async () => {
+    try {
+      await program.methods
+        .removeMint()
+        .accounts({
+          human,
+          vault,
+          mintConfig: mintConfigPda,
+          vaultTokenAta: vaultEurcAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("MintBalanceNotEmpty");
+    }
+  });
+
+  it("Token-2022 mint with a transfer fee: tiers use the amount received", async () => {
+    // 1% transfer fee withheld by the mint itself
+    const feeMintKp = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: mintAuthority.publicKey,
+          newAccountPubkey: feeMintKp.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMintKp.publicKey, mintAuthority.publicKey, mintAuthority.publicKey, 100, BigInt(1_000_000_000), TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(feeMintKp.publicKey, 6, mintAuthority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [mintAuthority, feeMintKp]
+    );
+    const feeMint = feeMintKp.publicKey;
+
+    const [feeMintConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_config"), vault.toBuffer(), feeMint.toBuffer()],
+      program.programId
+    );
+    const vaultFeeAta = getAssociatedTokenAddressSync(feeMint, vault, true, TOKEN_2022_PROGRAM_ID);
+    const recipientFeeAta = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, mintAuthority, feeMint, recipient.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    )).address;
+    const buybackFeeAta = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, mintAuthority, feeMint, buybackWallet.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    )).address;
+
+    await program.methods
+      .addMint(new BN(10_000_000), new BN(10_000_000))
+      .accounts({
+        human,
+        vault,
+        mint: feeMint,
+        mintConfig: feeMintConfig,
+        vaultTokenAta: vaultFeeAta,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    await mintTo(
+      provider.connection, mintAuthority, feeMint, vaultFeeAta, mintAuthority, 100_000_000, [], undefined, TOKEN_2022_PROGRAM_ID
+    );
+
+    // 10.1 sent, 1% withheld: 9.999 received, which fits tier 1 (10)
+    const before = await getAccount(provider.connection, recipientFeeAta, undefined, TOKEN_2022_PROGRAM_ID);
+    await program.methods
+      .sendToken(new BN(10_100_000), false, "Fee-bearing mint payment", null)
+      .accounts({
+        signer: agent.publicKey,
+        vault,
+        mint: feeMint,
+        mintConfig: feeMintConfig,
+        vaultTokenAta: vaultFeeAta,
+        recipientAta: recipientFeeAta,
+        agentRole: null,
+        protocolConfig,
+        buybackAta,
+        tokenBuybackAta: buybackFeeAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+    const after = await getAccount(provider.connection, recipientFeeAta, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(9_999_000);
+
+    const config = await program.account.mintConfig.fetch(feeMintConfig);
+    expect(config.dailyBudget.spent.toNumber()).to.equal(9_999_000);
+  });
+
+  // --- Batch payout tests ---
+
+  function batchLines(recipientAtas: PublicKey[]) {
+    return recipientAtas.flatMap((ata) => [
+      { pubkey: ata, isWritable: true, isSigner: false },
+      { pubkey: program.programId, isWritable: false, isSigner: false }, // no whitelist entry
+    ]);
+  }
+
+  function batchAccounts() {
+    return {
+      signer: agent.publicKey,
+      vault,
+      usdcMint,
+      vaultUsdcAta,
+      agentRole: null,
+      ...feeAccounts(),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      memoProgram: MEMO_PROGRAM_ID,
+    };
+  }
+
+  let secondRecipientAta: PublicKey;
+
+  it("Agent pays two recipients in one batch with a single fee", async () => {
+    const secondRecipient = Keypair.generate();
+    secondRecipientAta = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, mintAuthority, usdcMint, secondRecipient.publicKey
+    )).address;
+
+    const beforeFirst = await getAccount(provider.connection, recipientAta);
+    const beforeSecond = await getAccount(provider.connection, secondRecipientAta);
+    const beforeBuyback = await getAccount(provider.connection, buybackAta);
+    const beforeStaker = await getAccount(provider.connection, stakerRewardAta);
+
+    // 40 + 30 = 70 USDC, within tier 1 (75) as a whole
+    const sig = await program.methods
+      .sendUsdcBatch([new BN(40_000_000), new BN(30_000_000)], false, "October contractors", "INV-0043")
+      .accounts(batchAccounts())
+      .remainingAccounts(batchLines([recipientAta, secondRecipientAta]))
+      .signers([agent])
+      .rpc({ commitment: "confirmed" });
+    const tx = await provider.connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(tx.meta.logMessages.join("\n")).to.include("October contractors | invoice:INV-0043");
+
+    const afterFirst = await getAccount(provider.connection, recipientAta);
+    const afterSecond = await getAccount(provider.connection, secondRecipientAta);
+    expect(Number(afterFirst.amount) - Number(beforeFirst.amount)).to.equal(40_000_000);
+    expect(Number(afterSecond.amount) - Number(beforeSecond.amount)).to.equal(30_000_000);
+
+    // Fee = 70_000_000 * 25 / 10000 = 175_000, charged once
+    const afterBuyback = await getAccount(provider.connection, buybackAta);
+    const afterStaker = await getAccount(provider.connection, stakerRewardAta);
+    const feePaid =
+      Number(afterBuyback.amount) - Number(beforeBuyback.amount) +
+      Number(afterStaker.amount) - Number(beforeStaker.amount);
+    expect(feePaid).to.equal(175_000);
+  });
+
+  it("Batch total over tier 1 needs the emergency flag; nothing moves on failure", async () => {
+    const beforeFirst = await getAccount(provider.connection, recipientAta);
+
+    // Each line fits tier 1 (75) but the total (100) is tier 2
+    try {
+      await program.methods
+        .sendUsdcBatch([new BN(50_000_000), new BN(50_000_000)], false, "Over tier 1 as a whole", null)
+        .accounts(batchAccounts())
+        .remainingAccounts(batchLines([recipientAta, secondRecipientAta]))
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("NotEmergency");
+    }
+
+    const afterFirst = await getAccount(provider.connection, recipientAta);
+    expect(afterFirst.amount.toString()).to.equal(beforeFirst.amount.toString());
+  });
+
+  it("Batch line over tier2_max fails (TierTooHigh)", async () => {
+    try {
+      await program.methods
+        .sendUsdcBatch([new BN(1_000_000), new BN(200_000_000)], true, "One line too large", null)
+        .accounts(batchAccounts())
+        .remainingAccounts(batchLines([recipientAta, secondRecipientAta]))
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("TierTooHigh");
+    }
+  });
+
+  // --- Subscription tests ---
+
+  let subscriptionPda: PublicKey;
+
+  function subscriptionPayAccounts(cranker: PublicKey) {
+    return {
+      cranker,
+      vault,
+      subscription: subscriptionPda,
+      usdcMint,
+      vaultUsdcAta,
+      recipientAta,
+      ...feeAccounts(),
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  it("Human creates a subscription and anyone cranks the due payment", async () => {
+    const v = await program.account.vault.fetch(vault);
+    [subscriptionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), vault.toBuffer(), v.subscriptionCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+
+    const createAccounts = {
+      human,
+      vault,
+      recipient: recipient.publicKey,
+      recipientAta,
+      subscription: subscriptionPda,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // A start in the past would let a cranker pay the missed intervals at once
+    try {
+      await program.methods
+        .createSubscription(new BN(5_000_000), new BN(3600), new BN(now - 7200), null, 12)
+        .accounts(createAccounts)
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidSchedule");
+    }
+
+    await program.methods
+      .createSubscription(new BN(5_000_000), new BN(3600), new BN(now + 2), null, 12)
+      .accounts(createAccounts)
+      .rpc();
+
+    await new Promise((r) => setTimeout(r, 3000));
+    const before = await getAccount(provider.connection, recipientAta);
+    const budgetBefore = (await program.account.vault.fetch(vault)).dailyBudget.spent.toString();
+
+    await program.methods
+      .paySubscription()
+      .accounts(subscriptionPayAccounts(recipient.publicKey))
+      .signers([recipient])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(5_000_000);
+
+    const sub = await program.account.subscription.fetch(subscriptionPda);
+    expect(sub.paymentsMade).to.equal(1);
+    expect(sub.nextPaymentAt.toNumber()).to.equal(now + 2 + 3600);
+
+    // Subscription payments don't burn the agent's budget
+    const budgetAfter = (await program.account.vault.fetch(vault)).dailyBudget.spent.toString();
+    expect(budgetAfter).to.equal(budgetBefore);
+    await expectReconciled();
+  });
+
+  it("Cranking before the next interval fails (SubscriptionNotDue)", async () => {
+    try {
+      await program.methods
+        .paySubscription()
+        .accounts(subscriptionPayAccounts(recipient.publicKey))
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("SubscriptionNotDue");
+    }
+  });
+
+  it("Human pauses and then cancels the subscription", async () => {
+    await program.methods
+      .setSubscriptionPaused(true)
+      .accounts({ human, vault, subscription: subscriptionPda })
+      .rpc();
+    try {
+      await program.methods
+        .paySubscription()
+        .accounts(subscriptionPayAccounts(recipient.publicKey))
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("SubscriptionPaused");
+    }
+
+    await program.methods
+      .cancelSubscription()
+      .accounts({ human, vault, subscription: subscriptionPda })
+      .rpc();
+    expect(await provider.connection.getAccountInfo(subscriptionPda)).to.be.null;
+  });
+
+  // --- Stream tests ---
+
+  let streamPda: PublicKey;
+  let streamAta: PublicKey;
+
+  function streamSettleAccounts() {
+    return {
+      vault,
+      stream: streamPda,
+      usdcMint,
+      streamAta,
+      recipientAta,
+      ...feeAccounts(),
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  it("Human opens a stream; its full amount plus the fee moves out of the vault", async () => {
+    const v = await program.account.vault.fetch(vault);
+    [streamPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stream"), vault.toBuffer(), v.streamCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    streamAta = getAssociatedTokenAddressSync(usdcMint, streamPda, true);
+    const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+
+    // A stream can't start in the past
+    try {
+      await program.methods
+        .createStream(new BN(1_000), new BN(now - 60), new BN(now + 60), false)
+        .accounts({
+          creator: human,
+          vault,
+          agentRole: null,
+          usdcMint,
+          vaultUsdcAta,
+          recipient: recipient.publicKey,
+          recipientAta,
+          stream: streamPda,
+          streamAta,
+          protocolConfig,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("InvalidSchedule");
+    }
+
+    // 0.001 USDC per second for 60 seconds
+    await program.methods
+      .createStream(new BN(1_000), new BN(now + 2), new BN(now + 62), false)
+      .accounts({
+        creator: human,
+        vault,
+        agentRole: null,
+        usdcMint,
+        vaultUsdcAta,
+        recipient: recipient.publicKey,
+        recipientAta,
+        stream: streamPda,
+        streamAta,
+        protocolConfig,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const stream = await program.account.stream.fetch(streamPda);
+    expect(stream.deposited.toNumber()).to.equal(60_000);
+    // 0.25% of 60_000 is prefunded for the fee
+    expect(stream.feeReserve.toNumber()).to.equal(150);
+    const escrowed = await getAccount(provider.connection, streamAta);
+    expect(Number(escrowed.amount)).to.equal(60_150);
+    await expectReconciled();
+  });
+
+  it("Recipient withdraws what has vested so far; the fee comes from the stream", async () => {
+    await new Promise((r) => setTimeout(r, 5000));
+    const before = await getAccount(provider.connection, recipientAta);
+    const vaultBefore = await getAccount(provider.connection, vaultUsdcAta);
+
+    await program.methods
+      .withdrawStream()
+      .accounts({ recipient: recipient.publicKey, ...streamSettleAccounts() })
+      .signers([recipient])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    const paid = Number(after.amount) - Number(before.amount);
+    expect(paid).to.be.greaterThan(0);
+    expect(paid).to.be.lessThan(60_000);
+
+    const stream = await program.account.stream.fetch(streamPda);
+    expect(stream.withdrawn.toNumber()).to.equal(paid);
+    expect(stream.feeReserve.toNumber()).to.equal(150 - Math.floor((paid * FEE_BPS) / 10_000));
+    const vaultAfter = await getAccount(provider.connection, vaultUsdcAta);
+    expect(vaultAfter.amount).to.equal(vaultBefore.amount);
+    await expectReconciled();
+  });
+
+  it("Only the creator or the human can cancel (OnlyStreamCreator)", async () => {
+    try {
+      await program.methods
+        .cancelStream()
+        .accounts({
+          signer: recipient.publicKey,
+          creator: human,
+          agentRole: null,
+          vaultUsdcAta,
+          ...streamSettleAccounts(),
+        })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("OnlyStreamCreator");
+    }
+  });
+
+  it("Creator cancels: vested part paid out, the rest returns to the vault", async () => {
+    const vaultBefore = await getAccount(provider.connection, vaultUsdcAta);
+    const recipientBefore = await getAccount(provider.connection, recipientAta);
+    const escrowed = await getAccount(provider.connection, streamAta);
+
+    await program.methods
+      .cancelStream()
+      .accounts({ signer: human, creator: human, agentRole: null, vaultUsdcAta, ...streamSettleAccounts() })
+      .rpc();
+
+    const vaultAfter = await getAccount(provider.connection, vaultUsdcAta);
+    const recipientAfter = await getAccount(provider.connection, recipientAta);
+    const paidOut = Number(recipientAfter.amount) - Number(recipientBefore.amount);
+    // Vault gets back the unvested part and the unused fee reserve
+    const fee = Math.floor((paidOut * FEE_BPS) / 10_000);
+    expect(Number(vaultAfter.amount) - Number(vaultBefore.amount)).to.equal(
+      Number(escrowed.amount) - paidOut - fee
+    );
+
+    expect(await provider.connection.getAccountInfo(streamPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(streamAta)).to.be.null;
+    await expectReconciled();
+  });
+
+  // --- Escrow tests ---
+
+  const arbiter = Keypair.generate();
+
+  function escrowPdas(escrowCount: BN) {
+    const [escrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), vault.toBuffer(), escrowCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    return { escrow, escrowAta: getAssociatedTokenAddressSync(usdcMint, escrow, true) };
+  }
+
+  async function createEscrow(amount: number, deadline: number) {
+    const v = await program.account.vault.fetch(vault);
+    const { escrow, escrowAta } = escrowPdas(v.escrowCount);
+    await program.methods
+      .createEscrow(new BN(amount), new BN(deadline), arbiter.publicKey, false)
+      .accounts({
+        payer: agent.publicKey,
+        vault,
+        agentRole: null,
+        usdcMint,
+        vaultUsdcAta,
+        payee: recipient.publicKey,
+        payeeAta: recipientAta,
+        escrow,
+        escrowAta,
+        protocolConfig,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+    return { escrow, escrowAta };
+  }
+
+  it("Agent escrows a payment and releases it on delivery", async () => {
+    const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+    const { escrow, escrowAta } = await createEscrow(10_000_000, now + 3600);
+
+    // 0.25% of 10 USDC is locked alongside for the fee
+    const locked = await getAccount(provider.connection, escrowAta);
+    expect(Number(locked.amount)).to.equal(10_025_000);
+    const state = await program.account.escrow.fetch(escrow);
+    expect(state.feeReserve.toNumber()).to.equal(25_000);
+    await expectReconciled();
+
+    const before = await getAccount(provider.connection, recipientAta);
+    const vaultBefore = await getAccount(provider.connection, vaultUsdcAta);
+    await program.methods
+      .releaseEscrow()
+      .accounts({
+        signer: agent.publicKey,
+        payer: agent.publicKey,
+        vault,
+        escrow,
+        agentRole: null,
+        usdcMint,
+        escrowAta,
+        payeeAta: recipientAta,
+        ...feeAccounts(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+
+    const after = await getAccount(provider.connection, recipientAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(10_000_000);
+    const vaultAfter = await getAccount(provider.connection, vaultUsdcAta);
+    expect(vaultAfter.amount).to.equal(vaultBefore.amount);
+    expect(await provider.connection.getAccountInfo(escrow)).to.be.null;
+    expect(await provider.connection.getAccountInfo(escrowAta)).to.be.null;
+    await expectReconciled();
+  });
+
+  it("Payer can't refund before the deadline; the arbiter can", async () => {
+    const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+    const { escrow, escrowAta } = await createEscrow(5_000_000, now + 3600);
+    const refundAccounts = (signer: PublicKey) => ({
+      signer,
+      payer: agent.publicKey,
+      vault,
+      escrow,
+      agentRole: null,
+      usdcMint,
+      escrowAta,
+      vaultUsdcAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    try {
+      await program.methods
+        .refundEscrow()
+        .accounts(refundAccounts(agent.publicKey))
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("EscrowNotExpired");
+    }
+
+    try {
+      await program.methods
+        .refundEscrow()
+        .accounts(refundAccounts(recipient.publicKey))
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("NotEscrowParty");
+    }
+
+    const before = await getAccount(provider.connection, vaultUsdcAta);
+    await program.methods
+      .refundEscrow()
+      .accounts(refundAccounts(arbiter.publicKey))
+      .signers([arbiter])
+      .rpc();
+
+    const after = await getAccount(provider.connection, vaultUsdcAta);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(5_000_000);
+    expect(await provider.connection.getAccountInfo(escrow)).to.be.null;
+    await expectReconciled();
+  });
+
+  // --- Fee precision tests ---
+
+  it("Tiny amount: fee rounds to 0, no fee transfers", async () => {
+    // 100 lamports = 0.0001 USDC. fee = 100 * 25 / 10000 = 0
     const amount = new BN(100);
     const beforeStakerReward = await getAccount(provider.connection, stakerRewardAta);
     const beforeBuyback = await getAccount(provider.connection, buybackAta);
 
     await program.methods
-      .sendUsdc(amount, false)
+      .sendUsdc(amount, false, "Test payment", null)
       .accounts({
         signer: agent.publicKey,
         vault,
+        usdcMint,
         vaultUsdcAta,
         recipientAta,
+        recipient: null,
         whitelistEntry: null,
+        agentRole: null,
         ...feeAccounts(),
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
       })
       .signers([agent])
       .rpc();
@@ -598,15 +3064,19 @@ describe("tandem-wallet", () => {
     const beforeBuyback = await getAccount(provider.connection, buybackAta);
 
     await program.methods
-      .sendUsdc(amount, true) // tier 2 with emergency
+      .sendUsdc(amount, true, "Test payment", null) // tier 2 with emergency
       .accounts({
         signer: agent.publicKey,
         vault,
+        usdcMint,
         vaultUsdcAta,
         recipientAta,
+        recipient: null,
         whitelistEntry: null,
+        agentRole: null,
         ...feeAccounts(),
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
       })
       .signers([agent])
       .rpc();
@@ -739,15 +3209,19 @@ describe("tandem-wallet", () => {
     // Send 50 USDC to generate fees while staker is staked
     const amount = new BN(50_000_000);
     await program.methods
-      .sendUsdc(amount, false)
+      .sendUsdc(amount, false, "Test payment", null)
       .accounts({
         signer: agent.publicKey,
         vault,
+        usdcMint,
         vaultUsdcAta,
         recipientAta,
+        recipient: null,
         whitelistEntry: null,
+        agentRole: null,
         ...feeAccounts(),
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
       })
       .signers([agent])
       .rpc();
@@ -764,6 +3238,7 @@ describe("tandem-wallet", () => {
         stakeAccount: stakeAccountPda,
         stakerRewardAta,
         stakerUsdcAta,
+        usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([staker])
@@ -790,6 +3265,7 @@ describe("tandem-wallet", () => {
           stakeAccount: stakeAccountPda,
           stakerRewardAta,
           stakerUsdcAta,
+          usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([staker])
@@ -823,4 +3299,147 @@ describe("tandem-wallet", () => {
       })
       .rpc();
   });
+
+  function recoveryRequestPda(forVault: PublicKey) {
+    const [recoveryRequest] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recovery"), forVault.toBuffer()],
+      program.programId
+    );
+    return recoveryRequest;
+  }
+
+  it("Vault with mints still configured can't be closed (VaultHasOpenAccounts)", async () => {
+    try {
+      await program.methods
+        .closeVault()
+        .accounts({
+          human,
+          vault,
+          recoveryRequest: recoveryRequestPda(vault),
+          usdcMint,
+          vaultUsdcAta,
+          humanUsdcAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("VaultHasOpenAccounts");
+    }
+  });
+
+  it("Human closes a vault: balance swept, accounts closed, rent returned", async () => {
+    const closingAgent = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(closingAgent.publicKey, 1e9)
+    );
+    const [closingVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), human.toBuffer(), closingAgent.publicKey.toBuffer()],
+      program.programId
+    );
+    const closingVaultAta = getAssociatedTokenAddressSync(usdcMint, closingVault, true);
+    await program.methods
+      .initialize(TIER1_MAX, TIER2_MAX)
+      .accounts({
+        human,
+        agent: closingAgent.publicKey,
+        usdcMint,
+        vault: closingVault,
+        vaultUsdcAta: closingVaultAta,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await mintTo(provider.connection, mintAuthority, usdcMint, humanUsdcAta, mintAuthority, 10_000_000);
+    await program.methods
+      .deposit(new BN(10_000_000), "To be swept")
+      .accounts({
+        depositor: human,
+        vault: closingVault,
+        usdcMint,
+        depositorAta: humanUsdcAta,
+        vaultUsdcAta: closingVaultAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const [role] = PublicKey.findProgramAddressSync(
+      [Buffer.from("agent_role"), closingVault.toBuffer(), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .addAgent(recipient.publicKey, new BN(1_000_000), new BN(1_000_000), false)
+      .accounts({ human, vault: closingVault, agentRole: role, systemProgram: SystemProgram.programId })
+      .rpc();
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), closingVault.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .propose(new BN(200_000_000), "Left open")
+      .accounts({
+        agent: closingAgent.publicKey,
+        vault: closingVault,
+        recipient: recipient.publicKey,
+        recipientAta,
+        proposal: proposalPda,
+        agentRole: null,
+        mintConfig: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([closingAgent])
+      .rpc();
+
+    const closeAccounts = {
+      human,
+      vault: closingVault,
+      recoveryRequest: recoveryRequestPda(closingVault),
+      usdcMint,
+      vaultUsdcAta: closingVaultAta,
+      humanUsdcAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // A pending proposal left out would outlive the vault
+    try {
+      await program.methods
+        .closeVault()
+        .accounts(closeAccounts)
+        .remainingAccounts([{ pubkey: role, isWritable: true, isSigner: false }])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("VaultHasOpenAccounts");
+    }
+
+    // Agent roles must all be passed so none outlive the vault
+    try {
+      await program.methods
+        .closeVault()
+        .accounts(closeAccounts)
+        .remainingAccounts([{ pubkey: proposalPda, isWritable: true, isSigner: false }])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.error.errorCode.code).to.equal("VaultHasOpenAccounts");
+    }
+
+    const beforeHuman = await getAccount(provider.connection, humanUsdcAta);
+    await program.methods
+      .closeVault()
+      .accounts(closeAccounts)
+      .remainingAccounts([
+        { pubkey: proposalPda, isWritable: true, isSigner: false },
+        { pubkey: role, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    const afterHuman = await getAccount(provider.connection, humanUsdcAta);
+    expect(Number(afterHuman.amount) - Number(beforeHuman.amount)).to.equal(10_000_000);
+    for (const closed of [closingVault, closingVaultAta, proposalPda, role]) {
+      expect(await provider.connection.getAccountInfo(closed)).to.be.null;
+    }
+  });
 });